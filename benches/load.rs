@@ -0,0 +1,49 @@
+//! Measures how long loading the compiled entries in `tests/` takes.
+//! Run with `cargo bench`.
+
+use std::fs;
+use std::hint::black_box;
+use std::path::PathBuf;
+use std::time::Instant;
+
+use termini::{TermInfo, TermInfoRef};
+
+const ITERATIONS: u32 = 100;
+
+fn bench(name: &str, entries: usize, mut f: impl FnMut()) {
+    // warm up the page cache
+    f();
+    let start = Instant::now();
+    for _ in 0..ITERATIONS {
+        f();
+    }
+    let elapsed = start.elapsed() / ITERATIONS;
+    let per_entry = elapsed / entries as u32;
+    println!("{name:<24} {elapsed:>12.2?} total {per_entry:>10.2?} per entry");
+}
+
+fn main() {
+    let paths: Vec<PathBuf> = fs::read_dir(concat!(env!("CARGO_MANIFEST_DIR"), "/tests"))
+        .unwrap()
+        .map(|entry| entry.unwrap().path())
+        .filter(|path| path.is_file())
+        .collect();
+    let data: Vec<Vec<u8>> = paths.iter().map(|path| fs::read(path).unwrap()).collect();
+    println!("{} entries, {ITERATIONS} iterations", paths.len());
+
+    bench("TermInfo::from_path", paths.len(), || {
+        for path in &paths {
+            black_box(TermInfo::from_path(path).unwrap());
+        }
+    });
+    bench("TermInfo::parse", paths.len(), || {
+        for data in &data {
+            black_box(TermInfo::parse(&**data).unwrap());
+        }
+    });
+    bench("TermInfoRef::parse", paths.len(), || {
+        for data in &data {
+            black_box(TermInfoRef::parse(data).unwrap());
+        }
+    });
+}
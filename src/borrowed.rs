@@ -0,0 +1,142 @@
+//! Compiled entries borrowed from a byte slice, see [`TermInfoRef`]
+
+use crate::parsing::Sections;
+use crate::source::{ABSENT_NUMBER, CANCELLED_NUMBER};
+use crate::{
+    get_str_with_offset, string_value, BoolCapability, Extended, NumberCapability,
+    StringCapability, TermInfo, TermInfoData, Value, ValueStorage,
+};
+
+/// A compiled terminfo entry that borrows all of its data from the compiled bytes,
+/// created by [`TermInfoRef::parse`]. Parsing only validates the entry, capabilities
+/// are decoded when they are accessed.
+///
+/// Use `TermInfo::from` to convert it to an owned [`TermInfo`].
+#[derive(Debug, Clone, Copy)]
+pub struct TermInfoRef<'a> {
+    pub name: &'a str,
+    pub description: &'a str,
+    /// the names section (`name|aliases|description`)
+    pub(crate) names: &'a str,
+    pub(crate) data: Sections<'a>,
+    pub(crate) extended: ExtendedRef<'a>,
+}
+
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct ExtendedRef<'a> {
+    /// the string offsets contain the offsets of the string values followed by the
+    /// offsets of all names, `str_table` only contains the string values
+    pub(crate) data: Sections<'a>,
+    pub(crate) string_count: usize,
+    /// the names of the capabilities, stored after the string values
+    pub(crate) names: &'a [u8],
+}
+
+impl<'a> ExtendedRef<'a> {
+    /// Returns the present and cancelled capabilities with their names in the order
+    /// they are stored in
+    fn capabilities(&self) -> impl Iterator<Item = (&'a str, ValueStorage)> + 'a {
+        let data = self.data;
+        let names = self.names;
+        let bools = data.bools().map(|val| val.then_some(ValueStorage::True));
+        let numbers = data.numbers().map(|val| match val {
+            ABSENT_NUMBER => None,
+            CANCELLED_NUMBER => Some(ValueStorage::Cancelled),
+            _ => Some(ValueStorage::Number(val)),
+        });
+        let strings = (data.string_offsets().take(self.string_count)).map(|off| match off {
+            0xffff => None,
+            // cancelled capabilities of all types are stored as strings
+            0xfffe => Some(ValueStorage::Cancelled),
+            _ => Some(ValueStorage::String(off)),
+        });
+        let name_offsets = data.string_offsets().skip(self.string_count);
+        (bools.chain(numbers).chain(strings))
+            .zip(name_offsets)
+            .filter_map(move |(val, off)| {
+                // names are validated by the parser
+                let name = std::str::from_utf8(get_str_with_offset(names, off, 0)?).ok()?;
+                Some((name, val?))
+            })
+    }
+
+    fn value(&self, val: ValueStorage) -> Option<Value<'a>> {
+        let res = match val {
+            ValueStorage::True => Value::True,
+            ValueStorage::String(off) => {
+                string_value(get_str_with_offset(self.data.str_table, off, 0)?)
+            }
+            ValueStorage::Number(val) => Value::Number(val),
+            ValueStorage::Cancelled => return None,
+        };
+        Some(res)
+    }
+}
+
+impl<'a> TermInfoRef<'a> {
+    /// Returns the aliases of the entry (the names between the name and the description)
+    pub fn aliases(&self) -> impl Iterator<Item = &'a str> + 'a {
+        let count = self.names.matches('|').count().saturating_sub(1);
+        self.names.split('|').skip(1).take(count).map(str::trim)
+    }
+
+    /// Returns the string value for the capability, see [`TermInfo::raw_string_cap`]
+    pub fn raw_string_cap(&self, cap: StringCapability) -> Option<&'a [u8]> {
+        let off = self.data.string_offset(cap as usize)?;
+        get_str_with_offset(self.data.str_table, off, 0)
+    }
+
+    /// Returns the string value for the capability if it's valid UTF-8,
+    /// see [`TermInfo::utf8_string_cap`]
+    pub fn utf8_string_cap(&self, cap: StringCapability) -> Option<&'a str> {
+        std::str::from_utf8(self.raw_string_cap(cap)?).ok()
+    }
+
+    /// Returns the number value for the capability, see [`TermInfo::number_cap`]
+    pub fn number_cap(&self, cap: NumberCapability) -> Option<i32> {
+        Some(self.data.number(cap as usize)).filter(|&val| val >= 0)
+    }
+
+    /// Returns the bool value for the capability, see [`TermInfo::flag_cap`]
+    pub fn flag_cap(&self, cap: BoolCapability) -> bool {
+        self.data.bool(cap as usize)
+    }
+
+    /// Returns the value of the extended capability `name`, see [`TermInfo::extended_cap`]
+    pub fn extended_cap(&self, name: &str) -> Option<Value<'a>> {
+        let (_, val) = self.extended.capabilities().find(|&(cap, _)| cap == name)?;
+        self.extended.value(val)
+    }
+
+    /// Returns an iterator over all extended capabilities that are present in the
+    /// order they are stored in, which is usually sorted by name
+    pub fn extended_caps(&self) -> impl Iterator<Item = (&'a str, Value<'a>)> + 'a {
+        let extended = self.extended;
+        (extended.capabilities()).filter_map(move |(name, val)| Some((name, extended.value(val)?)))
+    }
+}
+
+impl From<TermInfoRef<'_>> for TermInfo {
+    fn from(info: TermInfoRef<'_>) -> TermInfo {
+        let data = info.data;
+        let extended = info.extended;
+        TermInfo {
+            name: info.name.to_owned(),
+            description: info.description.to_owned(),
+            aliases: info.aliases().map(str::to_owned).collect(),
+            data: TermInfoData {
+                bools: data.bools().collect(),
+                numbers: data.numbers().collect(),
+                strings: data.string_offsets().collect(),
+                str_table: data.str_table.into(),
+            },
+            extended: Extended {
+                capabilities: (extended.capabilities())
+                    .map(|(name, val)| (name.to_owned(), val))
+                    .collect(),
+                table: extended.data.str_table.into(),
+            },
+            path: None,
+        }
+    }
+}
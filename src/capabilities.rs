@@ -1,4 +1,7 @@
+use crate::Error;
+
 /// Known bool capabilities
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum BoolCapability {
     /// cub1 wraps from column 0 to last column
     AutoLeftMargin = 0,
@@ -77,6 +80,7 @@ pub enum BoolCapability {
 }
 
 /// Known number capabilities
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum NumberCapability {
     /// Number of columns in a line
     Columns = 0,
@@ -147,6 +151,7 @@ pub enum NumberCapability {
 }
 
 /// Known string capabilities
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum StringCapability {
     /// Back tab
     BackTab = 0,
@@ -937,3 +942,1624 @@ pub enum StringCapability {
     /// Set page length to #1 hundredth of an inch
     SetPageLenInch,
 }
+
+/// terminfo names of all capabilities in the order they are stored in compiled entries.
+/// The names of [`BoolCapability`] are followed by obsolete termcap capabilities.
+pub(crate) const BOOL_NAMES: [&str; 44] = [
+    "bw", "am", "xsb", "xhp", "xenl", "eo", "gn", "hc", "km", "hs", "in", "da", "db", "mir",
+    "msgr", "os", "eslok", "xt", "hz", "ul", "xon", "nxon", "mc5i", "chts", "nrrmc", "npc",
+    "ndscr", "ccc", "bce", "hls", "xhpa", "crxm", "daisy", "xvpa", "sam", "cpix", "lpix", "OTbs",
+    "OTns", "OTnc", "OTMT", "OTNL", "OTpt", "OTxr",
+];
+
+/// terminfo names of all capabilities in the order they are stored in compiled entries.
+/// The names of [`NumberCapability`] are followed by obsolete termcap capabilities.
+pub(crate) const NUMBER_NAMES: [&str; 39] = [
+    "cols", "it", "lines", "lm", "xmc", "pb", "vt", "wsl", "nlab", "lh", "lw", "ma", "wnum",
+    "colors", "pairs", "ncv", "bufsz", "spinv", "spinh", "maddr", "mjump", "mcs", "mls", "npins",
+    "orc", "orl", "orhi", "orvi", "cps", "widcs", "btns", "bitwin", "bitype", "OTug", "OTdC",
+    "OTdN", "OTdB", "OTdT", "OTkn",
+];
+
+/// terminfo names of all capabilities in the order they are stored in compiled entries.
+/// The names of [`StringCapability`] are followed by obsolete termcap capabilities.
+pub(crate) const STRING_NAMES: [&str; 414] = [
+    "cbt", "bel", "cr", "csr", "tbc", "clear", "el", "ed", "hpa", "cmdch", "cup", "cud1", "home",
+    "civis", "cub1", "mrcup", "cnorm", "cuf1", "ll", "cuu1", "cvvis", "dch1", "dl1", "dsl", "hd",
+    "smacs", "blink", "bold", "smcup", "smdc", "dim", "smir", "invis", "prot", "rev", "smso",
+    "smul", "ech", "rmacs", "sgr0", "rmcup", "rmdc", "rmir", "rmso", "rmul", "flash", "ff", "fsl",
+    "is1", "is2", "is3", "if", "ich1", "il1", "ip", "kbs", "ktbc", "kclr", "kctab", "kdch1",
+    "kdl1", "kcud1", "krmir", "kel", "ked", "kf0", "kf1", "kf10", "kf2", "kf3", "kf4", "kf5",
+    "kf6", "kf7", "kf8", "kf9", "khome", "kich1", "kil1", "kcub1", "kll", "knp", "kpp", "kcuf1",
+    "kind", "kri", "khts", "kcuu1", "rmkx", "smkx", "lf0", "lf1", "lf10", "lf2", "lf3", "lf4",
+    "lf5", "lf6", "lf7", "lf8", "lf9", "rmm", "smm", "nel", "pad", "dch", "dl", "cud", "ich",
+    "indn", "il", "cub", "cuf", "rin", "cuu", "pfkey", "pfloc", "pfx", "mc0", "mc4", "mc5", "rep",
+    "rs1", "rs2", "rs3", "rf", "rc", "vpa", "sc", "ind", "ri", "sgr", "hts", "wind", "ht", "tsl",
+    "uc", "hu", "iprog", "ka1", "ka3", "kb2", "kc1", "kc3", "mc5p", "rmp", "acsc", "pln", "kcbt",
+    "smxon", "rmxon", "smam", "rmam", "xonc", "xoffc", "enacs", "smln", "rmln", "kbeg", "kcan",
+    "kclo", "kcmd", "kcpy", "kcrt", "kend", "kent", "kext", "kfnd", "khlp", "kmrk", "kmsg", "kmov",
+    "knxt", "kopn", "kopt", "kprv", "kprt", "krdo", "kref", "krfr", "krpl", "krst", "kres", "ksav",
+    "kspd", "kund", "kBEG", "kCAN", "kCMD", "kCPY", "kCRT", "kDC", "kDL", "kslt", "kEND", "kEOL",
+    "kEXT", "kFND", "kHLP", "kHOM", "kIC", "kLFT", "kMSG", "kMOV", "kNXT", "kOPT", "kPRV", "kPRT",
+    "kRDO", "kRPL", "kRIT", "kRES", "kSAV", "kSPD", "kUND", "rfi", "kf11", "kf12", "kf13", "kf14",
+    "kf15", "kf16", "kf17", "kf18", "kf19", "kf20", "kf21", "kf22", "kf23", "kf24", "kf25", "kf26",
+    "kf27", "kf28", "kf29", "kf30", "kf31", "kf32", "kf33", "kf34", "kf35", "kf36", "kf37", "kf38",
+    "kf39", "kf40", "kf41", "kf42", "kf43", "kf44", "kf45", "kf46", "kf47", "kf48", "kf49", "kf50",
+    "kf51", "kf52", "kf53", "kf54", "kf55", "kf56", "kf57", "kf58", "kf59", "kf60", "kf61", "kf62",
+    "kf63", "el1", "mgc", "smgl", "smgr", "fln", "sclk", "dclk", "rmclk", "cwin", "wingo", "hup",
+    "dial", "qdial", "tone", "pulse", "hook", "pause", "wait", "u0", "u1", "u2", "u3", "u4", "u5",
+    "u6", "u7", "u8", "u9", "op", "oc", "initc", "initp", "scp", "setf", "setb", "cpi", "lpi",
+    "chr", "cvr", "defc", "swidm", "sdrfq", "sitm", "slm", "smicm", "snlq", "snrmq", "sshm",
+    "ssubm", "ssupm", "sum", "rwidm", "ritm", "rlm", "rmicm", "rshm", "rsubm", "rsupm", "rum",
+    "mhpa", "mcud1", "mcub1", "mcuf1", "mvpa", "mcuu1", "porder", "mcud", "mcub", "mcuf", "mcuu",
+    "scs", "smgb", "smgbp", "smglp", "smgrp", "smgt", "smgtp", "sbim", "scsd", "rbim", "rcsd",
+    "subcs", "supcs", "docr", "zerom", "csnm", "kmous", "minfo", "reqmp", "getm", "setaf", "setab",
+    "pfxl", "devt", "csin", "s0ds", "s1ds", "s2ds", "s3ds", "smglr", "smgtb", "birep", "binel",
+    "bicr", "colornm", "defbi", "endbi", "setcolor", "slines", "dispc", "smpch", "rmpch", "smsc",
+    "rmsc", "pctrm", "scesc", "scesa", "ehhlm", "elhlm", "elohlm", "erhlm", "ethlm", "evhlm",
+    "sgr1", "slength", "OTi2", "OTrs", "OTnl", "OTbc", "OTko", "OTma", "OTG2", "OTG3", "OTG1",
+    "OTG4", "OTGR", "OTGL", "OTGU", "OTGD", "OTGH", "OTGV", "OTGC", "meml", "memu", "box1",
+];
+
+macro_rules! capability_metadata {
+    (
+        $ty: ident,
+        $all: ident,
+        $names: ident,
+        $long_names: ident,
+        $termcap_names: ident,
+        $descriptions: ident
+    ) => {
+        impl $ty {
+            /// All capabilities in the order they are stored in compiled entries
+            pub const ALL: &'static [$ty] = &$all;
+
+            /// Returns the terminfo name of the capability (`cup`)
+            pub fn name(self) -> &'static str {
+                $names[self as usize]
+            }
+
+            /// Returns the long (variable) name of the capability (`cursor_address`)
+            pub fn long_name(self) -> &'static str {
+                $long_names[self as usize]
+            }
+
+            /// Returns the two letter termcap name of the capability (`cm`)
+            pub fn termcap_name(self) -> &'static str {
+                $termcap_names[self as usize]
+            }
+
+            /// Returns a short description of the capability
+            pub fn description(self) -> &'static str {
+                $descriptions[self as usize]
+            }
+
+            /// Looks up a capability by its terminfo name (`cup`)
+            pub fn from_name(name: &str) -> Option<$ty> {
+                let i = $all.iter().position(|cap| cap.name() == name)?;
+                Some($all[i])
+            }
+
+            /// Looks up a capability by its long (variable) name (`cursor_address`)
+            pub fn from_long_name(name: &str) -> Option<$ty> {
+                let i = $long_names.iter().position(|&it| it == name)?;
+                Some($all[i])
+            }
+
+            /// Looks up a capability by its termcap name (`cm`).
+            /// Termcap names may differ from terminfo names with the same spelling (`dl`).
+            /// If multiple capabilities share the same termcap name, the first one is returned.
+            pub fn from_termcap_name(name: &str) -> Option<$ty> {
+                let i = $termcap_names.iter().position(|&it| it == name)?;
+                Some($all[i])
+            }
+        }
+
+        impl TryFrom<usize> for $ty {
+            type Error = Error;
+
+            /// Converts the index of a capability in compiled entries to the capability
+            fn try_from(index: usize) -> Result<$ty, Error> {
+                $all.get(index)
+                    .copied()
+                    .ok_or(Error::InvalidCapabilityIndex(index))
+            }
+        }
+    };
+}
+
+capability_metadata!(
+    BoolCapability,
+    BOOLS,
+    BOOL_NAMES,
+    BOOL_LONG_NAMES,
+    BOOL_TERMCAP_NAMES,
+    BOOL_DESCRIPTIONS
+);
+capability_metadata!(
+    NumberCapability,
+    NUMBERS,
+    NUMBER_NAMES,
+    NUMBER_LONG_NAMES,
+    NUMBER_TERMCAP_NAMES,
+    NUMBER_DESCRIPTIONS
+);
+capability_metadata!(
+    StringCapability,
+    STRINGS,
+    STRING_NAMES,
+    STRING_LONG_NAMES,
+    STRING_TERMCAP_NAMES,
+    STRING_DESCRIPTIONS
+);
+
+/// long (variable) names of the [`BoolCapability`] variants
+const BOOL_LONG_NAMES: [&str; 37] = [
+    "auto_left_margin",
+    "auto_right_margin",
+    "no_esc_ctlc",
+    "ceol_standout_glitch",
+    "eat_newline_glitch",
+    "erase_overstrike",
+    "generic_type",
+    "hard_copy",
+    "has_meta_key",
+    "has_status_line",
+    "insert_null_glitch",
+    "memory_above",
+    "memory_below",
+    "move_insert_mode",
+    "move_standout_mode",
+    "over_strike",
+    "status_line_esc_ok",
+    "dest_tabs_magic_smso",
+    "tilde_glitch",
+    "transparent_underline",
+    "xon_xoff",
+    "needs_xon_xoff",
+    "prtr_silent",
+    "hard_cursor",
+    "non_rev_rmcup",
+    "no_pad_char",
+    "non_dest_scroll_region",
+    "can_change",
+    "back_color_erase",
+    "hue_lightness_saturation",
+    "col_addr_glitch",
+    "cr_cancels_micro_mode",
+    "has_print_wheel",
+    "row_addr_glitch",
+    "semi_auto_right_margin",
+    "cpi_changes_res",
+    "lpi_changes_res",
+];
+
+/// termcap names of the [`BoolCapability`] variants
+const BOOL_TERMCAP_NAMES: [&str; 37] = [
+    "bw", "am", "xb", "xs", "xn", "eo", "gn", "hc", "km", "hs", "in", "da", "db", "mi", "ms", "os",
+    "es", "xt", "hz", "ul", "xo", "nx", "5i", "HC", "NR", "NP", "ND", "cc", "ut", "hl", "YA", "YB",
+    "YC", "YD", "YE", "YF", "YG",
+];
+
+/// descriptions of the [`BoolCapability`] variants
+const BOOL_DESCRIPTIONS: [&str; 37] = [
+    "cub1 wraps from column 0 to last column",
+    "Terminal has automatic margins",
+    "Beehive (f1=escape, f2=ctrl C)",
+    "Standout not erased by overwriting (hp)",
+    "Newline ignored after 80 columns (Concept)",
+    "Can erase overstrikes with a blank",
+    "Generic line type (e.g., dialup, switch)",
+    "Hardcopy terminal",
+    "Has a meta key (shift, sets parity bit)",
+    "Has extra 'status line'",
+    "Insert mode distinguishes nulls",
+    "Display may be retained above the screen",
+    "Display may be retained below the screen",
+    "Safe to move while in insert mode",
+    "Safe to move in standout modes",
+    "Terminal overstrikes on hard-copy terminal",
+    "Escape can be used on the status line",
+    "Destructive tabs, magic smso char (t1061)",
+    "Hazeltine; can't print tilde (~)",
+    "Underline character overstrikes",
+    "Terminal uses xon/xoff handshaking",
+    "Padding won't work, xon/xoff required",
+    "Printer won't echo on screen",
+    "Cursor is hard to see",
+    "smcup does not reverse rmcup",
+    "Pad character doesn't exist",
+    "Scrolling region is nondestructive",
+    "Terminal can re-define existing colour",
+    "Screen erased with background colour",
+    "Terminal uses only HLS colour notation (Tektronix)",
+    "Only positive motion for hpa/mhpa caps",
+    "Using cr turns off micro mode",
+    "Printer needs operator to change character set",
+    "Only positive motion for vpa/mvpa caps",
+    "Printing in last column causes cr",
+    "Changing character pitch changes resolution",
+    "Changing line pitch changes resolution",
+];
+
+/// all [`BoolCapability`] variants in order
+pub(crate) const BOOLS: [BoolCapability; 37] = [
+    BoolCapability::AutoLeftMargin,
+    BoolCapability::AutoRightMargin,
+    BoolCapability::NoEscCtlc,
+    BoolCapability::CeolStandoutGlitch,
+    BoolCapability::EatNewlineGlitch,
+    BoolCapability::EraseOverstrike,
+    BoolCapability::GenericType,
+    BoolCapability::HardCopy,
+    BoolCapability::HasMetaKey,
+    BoolCapability::HasStatusLine,
+    BoolCapability::InsertNullGlitch,
+    BoolCapability::MemoryAbove,
+    BoolCapability::MemoryBelow,
+    BoolCapability::MoveInsertMode,
+    BoolCapability::MoveStandoutMode,
+    BoolCapability::OverStrike,
+    BoolCapability::StatusLineEscOk,
+    BoolCapability::DestTabsMagicSmso,
+    BoolCapability::TildeGlitch,
+    BoolCapability::TransparentUnderline,
+    BoolCapability::XonXoff,
+    BoolCapability::NeedsXonXoff,
+    BoolCapability::PrtrSilent,
+    BoolCapability::HardCursor,
+    BoolCapability::NonRevRmcup,
+    BoolCapability::NoPadChar,
+    BoolCapability::NonDestScrollRegion,
+    BoolCapability::CanChange,
+    BoolCapability::BackColorErase,
+    BoolCapability::HueLightnessSaturation,
+    BoolCapability::ColAddrGlitch,
+    BoolCapability::CrCancelsMicroMode,
+    BoolCapability::HasPrintWheel,
+    BoolCapability::RowAddrGlitch,
+    BoolCapability::SemiAutoRightMargin,
+    BoolCapability::CpiChangesRes,
+    BoolCapability::LpiChangesRes,
+];
+
+/// long (variable) names of the [`NumberCapability`] variants
+const NUMBER_LONG_NAMES: [&str; 33] = [
+    "columns",
+    "init_tabs",
+    "lines",
+    "lines_of_memory",
+    "magic_cookie_glitch",
+    "padding_baud_rate",
+    "virtual_terminal",
+    "width_status_line",
+    "num_labels",
+    "label_height",
+    "label_width",
+    "max_attributes",
+    "maximum_windows",
+    "max_colors",
+    "max_pairs",
+    "no_color_video",
+    "buffer_capacity",
+    "dot_vert_spacing",
+    "dot_horz_spacing",
+    "max_micro_address",
+    "max_micro_jump",
+    "micro_col_size",
+    "micro_line_size",
+    "number_of_pins",
+    "output_res_char",
+    "output_res_line",
+    "output_res_horz_inch",
+    "output_res_vert_inch",
+    "print_rate",
+    "wide_char_size",
+    "buttons",
+    "bit_image_entwining",
+    "bit_image_type",
+];
+
+/// termcap names of the [`NumberCapability`] variants
+const NUMBER_TERMCAP_NAMES: [&str; 33] = [
+    "co", "it", "li", "lm", "sg", "pb", "vt", "ws", "Nl", "lh", "lw", "ma", "MW", "Co", "pa", "NC",
+    "Ya", "Yb", "Yc", "Yd", "Ye", "Yf", "Yg", "Yh", "Yi", "Yj", "Yk", "Yl", "Ym", "Yn", "BT", "Yo",
+    "Yp",
+];
+
+/// descriptions of the [`NumberCapability`] variants
+const NUMBER_DESCRIPTIONS: [&str; 33] = [
+    "Number of columns in a line",
+    "Tabs initially every # spaces",
+    "Number of lines on a screen or a page",
+    "Lines of memory if > lines; 0 means varies",
+    "Number of blank characters left by smso or rmso",
+    "Lowest baud rate where padding needed",
+    "Virtual terminal number",
+    "Number of columns in status line",
+    "Number of labels on screen (start at 1)",
+    "Number of rows in each label",
+    "Number of columns in each label",
+    "Maximum combined video attributes terminal can display",
+    "Maximum number of definable windows",
+    "Maximum number of colours on the screen",
+    "Maximum number of colour-pairs on the screen",
+    "Video attributes that can't be used with colours",
+    "Number of bytes buffered before printing",
+    "Spacing of pins vertically in pins per inch",
+    "Spacing of dots horizontally in dots per inch",
+    "Maximum value in micro address",
+    "Maximum value in parm micro",
+    "Character step size when in micro mode",
+    "Line step size when in micro mode",
+    "Number of pins in print-head",
+    "Horizontal resolution in units per character",
+    "Vertical resolution in units per line",
+    "Horizontal resolution in units per inch",
+    "Vertical resolution in units per inch",
+    "Print rate in characters per second",
+    "Character step size when in double-wide mode",
+    "Number of buttons on the mouse",
+    "Number of passes for each bit-map row",
+    "Type of bit image device",
+];
+
+/// all [`NumberCapability`] variants in order
+pub(crate) const NUMBERS: [NumberCapability; 33] = [
+    NumberCapability::Columns,
+    NumberCapability::InitTabs,
+    NumberCapability::Lines,
+    NumberCapability::LinesOfMemory,
+    NumberCapability::MagicCookieGlitch,
+    NumberCapability::PaddingBaudRate,
+    NumberCapability::VirtualTerminal,
+    NumberCapability::WidthStatusLine,
+    NumberCapability::NumLabels,
+    NumberCapability::LabelHeight,
+    NumberCapability::LabelWidth,
+    NumberCapability::MaxAttributes,
+    NumberCapability::MaximumWindows,
+    NumberCapability::MaxColors,
+    NumberCapability::MaxPairs,
+    NumberCapability::NoColorVideo,
+    NumberCapability::BufferCapacity,
+    NumberCapability::DotVertSpacing,
+    NumberCapability::DotHorzSpacing,
+    NumberCapability::MaxMicroAddress,
+    NumberCapability::MaxMicroJump,
+    NumberCapability::MicroColSize,
+    NumberCapability::MicroLineSize,
+    NumberCapability::NumberOfPins,
+    NumberCapability::OutputResChar,
+    NumberCapability::OutputResLine,
+    NumberCapability::OutputResHorzInch,
+    NumberCapability::OutputResVertInch,
+    NumberCapability::PrintRate,
+    NumberCapability::WideCharSize,
+    NumberCapability::Buttons,
+    NumberCapability::BitImageEntwining,
+    NumberCapability::BitImageType,
+];
+
+/// long (variable) names of the [`StringCapability`] variants
+const STRING_LONG_NAMES: [&str; 394] = [
+    "back_tab",
+    "bell",
+    "carriage_return",
+    "change_scroll_region",
+    "clear_all_tabs",
+    "clear_screen",
+    "clr_eol",
+    "clr_eos",
+    "column_address",
+    "command_character",
+    "cursor_address",
+    "cursor_down",
+    "cursor_home",
+    "cursor_invisible",
+    "cursor_left",
+    "cursor_mem_address",
+    "cursor_normal",
+    "cursor_right",
+    "cursor_to_ll",
+    "cursor_up",
+    "cursor_visible",
+    "delete_character",
+    "delete_line",
+    "dis_status_line",
+    "down_half_line",
+    "enter_alt_charset_mode",
+    "enter_blink_mode",
+    "enter_bold_mode",
+    "enter_ca_mode",
+    "enter_delete_mode",
+    "enter_dim_mode",
+    "enter_insert_mode",
+    "enter_secure_mode",
+    "enter_protected_mode",
+    "enter_reverse_mode",
+    "enter_standout_mode",
+    "enter_underline_mode",
+    "erase_chars",
+    "exit_alt_charset_mode",
+    "exit_attribute_mode",
+    "exit_ca_mode",
+    "exit_delete_mode",
+    "exit_insert_mode",
+    "exit_standout_mode",
+    "exit_underline_mode",
+    "flash_screen",
+    "form_feed",
+    "from_status_line",
+    "init_1string",
+    "init_2string",
+    "init_3string",
+    "init_file",
+    "insert_character",
+    "insert_line",
+    "insert_padding",
+    "key_backspace",
+    "key_catab",
+    "key_clear",
+    "key_ctab",
+    "key_dc",
+    "key_dl",
+    "key_down",
+    "key_eic",
+    "key_eol",
+    "key_eos",
+    "key_f0",
+    "key_f1",
+    "key_f10",
+    "key_f2",
+    "key_f3",
+    "key_f4",
+    "key_f5",
+    "key_f6",
+    "key_f7",
+    "key_f8",
+    "key_f9",
+    "key_home",
+    "key_ic",
+    "key_il",
+    "key_left",
+    "key_ll",
+    "key_npage",
+    "key_ppage",
+    "key_right",
+    "key_sf",
+    "key_sr",
+    "key_stab",
+    "key_up",
+    "keypad_local",
+    "keypad_xmit",
+    "lab_f0",
+    "lab_f1",
+    "lab_f10",
+    "lab_f2",
+    "lab_f3",
+    "lab_f4",
+    "lab_f5",
+    "lab_f6",
+    "lab_f7",
+    "lab_f8",
+    "lab_f9",
+    "meta_off",
+    "meta_on",
+    "newline",
+    "pad_char",
+    "parm_dch",
+    "parm_delete_line",
+    "parm_down_cursor",
+    "parm_ich",
+    "parm_index",
+    "parm_insert_line",
+    "parm_left_cursor",
+    "parm_right_cursor",
+    "parm_rindex",
+    "parm_up_cursor",
+    "pkey_key",
+    "pkey_local",
+    "pkey_xmit",
+    "print_screen",
+    "prtr_off",
+    "prtr_on",
+    "repeat_char",
+    "reset_1string",
+    "reset_2string",
+    "reset_3string",
+    "reset_file",
+    "restore_cursor",
+    "row_address",
+    "save_cursor",
+    "scroll_forward",
+    "scroll_reverse",
+    "set_attributes",
+    "set_tab",
+    "set_window",
+    "tab",
+    "to_status_line",
+    "underline_char",
+    "up_half_line",
+    "init_prog",
+    "key_a1",
+    "key_a3",
+    "key_b2",
+    "key_c1",
+    "key_c3",
+    "prtr_non",
+    "char_padding",
+    "acs_chars",
+    "plab_norm",
+    "key_btab",
+    "enter_xon_mode",
+    "exit_xon_mode",
+    "enter_am_mode",
+    "exit_am_mode",
+    "xon_character",
+    "xoff_character",
+    "ena_acs",
+    "label_on",
+    "label_off",
+    "key_beg",
+    "key_cancel",
+    "key_close",
+    "key_command",
+    "key_copy",
+    "key_create",
+    "key_end",
+    "key_enter",
+    "key_exit",
+    "key_find",
+    "key_help",
+    "key_mark",
+    "key_message",
+    "key_move",
+    "key_next",
+    "key_open",
+    "key_options",
+    "key_previous",
+    "key_print",
+    "key_redo",
+    "key_reference",
+    "key_refresh",
+    "key_replace",
+    "key_restart",
+    "key_resume",
+    "key_save",
+    "key_suspend",
+    "key_undo",
+    "key_sbeg",
+    "key_scancel",
+    "key_scommand",
+    "key_scopy",
+    "key_screate",
+    "key_sdc",
+    "key_sdl",
+    "key_select",
+    "key_send",
+    "key_seol",
+    "key_sexit",
+    "key_sfind",
+    "key_shelp",
+    "key_shome",
+    "key_sic",
+    "key_sleft",
+    "key_smessage",
+    "key_smove",
+    "key_snext",
+    "key_soptions",
+    "key_sprevious",
+    "key_sprint",
+    "key_sredo",
+    "key_sreplace",
+    "key_sright",
+    "key_srsume",
+    "key_ssave",
+    "key_ssuspend",
+    "key_sundo",
+    "req_for_input",
+    "key_f11",
+    "key_f12",
+    "key_f13",
+    "key_f14",
+    "key_f15",
+    "key_f16",
+    "key_f17",
+    "key_f18",
+    "key_f19",
+    "key_f20",
+    "key_f21",
+    "key_f22",
+    "key_f23",
+    "key_f24",
+    "key_f25",
+    "key_f26",
+    "key_f27",
+    "key_f28",
+    "key_f29",
+    "key_f30",
+    "key_f31",
+    "key_f32",
+    "key_f33",
+    "key_f34",
+    "key_f35",
+    "key_f36",
+    "key_f37",
+    "key_f38",
+    "key_f39",
+    "key_f40",
+    "key_f41",
+    "key_f42",
+    "key_f43",
+    "key_f44",
+    "key_f45",
+    "key_f46",
+    "key_f47",
+    "key_f48",
+    "key_f49",
+    "key_f50",
+    "key_f51",
+    "key_f52",
+    "key_f53",
+    "key_f54",
+    "key_f55",
+    "key_f56",
+    "key_f57",
+    "key_f58",
+    "key_f59",
+    "key_f60",
+    "key_f61",
+    "key_f62",
+    "key_f63",
+    "clr_bol",
+    "clear_margins",
+    "set_left_margin",
+    "set_right_margin",
+    "label_format",
+    "set_clock",
+    "display_clock",
+    "remove_clock",
+    "create_window",
+    "goto_window",
+    "hangup",
+    "dial_phone",
+    "quick_dial",
+    "tone",
+    "pulse",
+    "flash_hook",
+    "fixed_pause",
+    "wait_tone",
+    "user0",
+    "user1",
+    "user2",
+    "user3",
+    "user4",
+    "user5",
+    "user6",
+    "user7",
+    "user8",
+    "user9",
+    "orig_pair",
+    "orig_colors",
+    "initialize_color",
+    "initialize_pair",
+    "set_color_pair",
+    "set_foreground",
+    "set_background",
+    "change_char_pitch",
+    "change_line_pitch",
+    "change_res_horz",
+    "change_res_vert",
+    "define_char",
+    "enter_doublewide_mode",
+    "enter_draft_quality",
+    "enter_italics_mode",
+    "enter_leftward_mode",
+    "enter_micro_mode",
+    "enter_near_letter_quality",
+    "enter_normal_quality",
+    "enter_shadow_mode",
+    "enter_subscript_mode",
+    "enter_superscript_mode",
+    "enter_upward_mode",
+    "exit_doublewide_mode",
+    "exit_italics_mode",
+    "exit_leftward_mode",
+    "exit_micro_mode",
+    "exit_shadow_mode",
+    "exit_subscript_mode",
+    "exit_superscript_mode",
+    "exit_upward_mode",
+    "micro_column_address",
+    "micro_down",
+    "micro_left",
+    "micro_right",
+    "micro_row_address",
+    "micro_up",
+    "order_of_pins",
+    "parm_down_micro",
+    "parm_left_micro",
+    "parm_right_micro",
+    "parm_up_micro",
+    "select_char_set",
+    "set_bottom_margin",
+    "set_bottom_margin_parm",
+    "set_left_margin_parm",
+    "set_right_margin_parm",
+    "set_top_margin",
+    "set_top_margin_parm",
+    "start_bit_image",
+    "start_char_set_def",
+    "stop_bit_image",
+    "stop_char_set_def",
+    "subscript_characters",
+    "superscript_characters",
+    "these_cause_cr",
+    "zero_motion",
+    "char_set_names",
+    "key_mouse",
+    "mouse_info",
+    "req_mouse_pos",
+    "get_mouse",
+    "set_a_foreground",
+    "set_a_background",
+    "pkey_plab",
+    "device_type",
+    "code_set_init",
+    "set0_des_seq",
+    "set1_des_seq",
+    "set2_des_seq",
+    "set3_des_seq",
+    "set_lr_margin",
+    "set_tb_margin",
+    "bit_image_repeat",
+    "bit_image_newline",
+    "bit_image_carriage_return",
+    "color_names",
+    "define_bit_image_region",
+    "end_bit_image_region",
+    "set_color_band",
+    "set_page_length",
+    "display_pc_char",
+    "enter_pc_charset_mode",
+    "exit_pc_charset_mode",
+    "enter_scancode_mode",
+    "exit_scancode_mode",
+    "pc_term_options",
+    "scancode_escape",
+    "alt_scancode_esc",
+    "enter_horizontal_hl_mode",
+    "enter_left_hl_mode",
+    "enter_low_hl_mode",
+    "enter_right_hl_mode",
+    "enter_top_hl_mode",
+    "enter_vertical_hl_mode",
+    "set_a_attributes",
+    "set_pglen_inch",
+];
+
+/// termcap names of the [`StringCapability`] variants
+const STRING_TERMCAP_NAMES: [&str; 394] = [
+    "bt", "bl", "cr", "cs", "ct", "cl", "ce", "cd", "ch", "CC", "cm", "do", "ho", "vi", "le", "CM",
+    "ve", "nd", "ll", "up", "vs", "dc", "dl", "ds", "hd", "as", "mb", "md", "ti", "dm", "mh", "im",
+    "mk", "mp", "mr", "so", "us", "ec", "ae", "me", "te", "ed", "ei", "se", "ue", "vb", "ff", "fs",
+    "i1", "is", "i3", "if", "ic", "al", "ip", "kb", "ka", "kC", "kt", "kD", "kL", "kd", "kM", "kE",
+    "kS", "k0", "k1", "k;", "k2", "k3", "k4", "k5", "k6", "k7", "k8", "k9", "kh", "kI", "kA", "kl",
+    "kH", "kN", "kP", "kr", "kF", "kR", "kT", "ku", "ke", "ks", "l0", "l1", "la", "l2", "l3", "l4",
+    "l5", "l6", "l7", "l8", "l9", "mo", "mm", "nw", "pc", "DC", "DL", "DO", "IC", "SF", "AL", "LE",
+    "RI", "SR", "UP", "pk", "pl", "px", "ps", "pf", "po", "rp", "r1", "r2", "r3", "rf", "rc", "cv",
+    "sc", "sf", "sr", "sa", "st", "wi", "ta", "ts", "uc", "hu", "iP", "K1", "K3", "K2", "K4", "K5",
+    "pO", "rP", "ac", "pn", "kB", "SX", "RX", "SA", "RA", "XN", "XF", "eA", "LO", "LF", "@1", "@2",
+    "@3", "@4", "@5", "@6", "@7", "@8", "@9", "@0", "%1", "%2", "%3", "%4", "%5", "%6", "%7", "%8",
+    "%9", "%0", "&1", "&2", "&3", "&4", "&5", "&6", "&7", "&8", "&9", "&0", "*1", "*2", "*3", "*4",
+    "*5", "*6", "*7", "*8", "*9", "*0", "#1", "#2", "#3", "#4", "%a", "%b", "%c", "%d", "%e", "%f",
+    "%g", "%h", "%i", "%j", "!1", "!2", "!3", "RF", "F1", "F2", "F3", "F4", "F5", "F6", "F7", "F8",
+    "F9", "FA", "FB", "FC", "FD", "FE", "FF", "FG", "FH", "FI", "FJ", "FK", "FL", "FM", "FN", "FO",
+    "FP", "FQ", "FR", "FS", "FT", "FU", "FV", "FW", "FX", "FY", "FZ", "Fa", "Fb", "Fc", "Fd", "Fe",
+    "Ff", "Fg", "Fh", "Fi", "Fj", "Fk", "Fl", "Fm", "Fn", "Fo", "Fp", "Fq", "Fr", "cb", "MC", "ML",
+    "MR", "Lf", "SC", "DK", "RC", "CW", "WG", "HU", "DI", "QD", "TO", "PU", "fh", "PA", "WA", "u0",
+    "u1", "u2", "u3", "u4", "u5", "u6", "u7", "u8", "u9", "op", "oc", "Ic", "Ip", "sp", "Sf", "Sb",
+    "ZA", "ZB", "ZC", "ZD", "ZE", "ZF", "ZG", "ZH", "ZI", "ZJ", "ZK", "ZL", "ZM", "ZN", "ZO", "ZP",
+    "ZQ", "ZR", "ZS", "ZT", "ZU", "ZV", "ZW", "ZX", "ZY", "ZZ", "Za", "Zb", "Zc", "Zd", "Ze", "Zf",
+    "Zg", "Zh", "Zi", "Zj", "Zk", "Zl", "Zm", "Zn", "Zo", "Zp", "Zq", "Zr", "Zs", "Zt", "Zu", "Zv",
+    "Zw", "Zx", "Zy", "Km", "Mi", "RQ", "Gm", "AF", "AB", "xl", "dv", "ci", "s0", "s1", "s2", "s3",
+    "ML", "MT", "Xy", "Zz", "Yv", "Yw", "Yx", "Yy", "Yz", "YZ", "S1", "S2", "S3", "S4", "S5", "S6",
+    "S7", "S8", "Xh", "Xl", "Xo", "Xr", "Xt", "Xv", "sA", "YI",
+];
+
+/// descriptions of the [`StringCapability`] variants
+const STRING_DESCRIPTIONS: [&str; 394] = [
+    "Back tab",
+    "Audible signal (bell)",
+    "Carriage return",
+    "Change to lines #1 through #2 (VT100)",
+    "Clear all tab stops",
+    "Clear screen and home cursor",
+    "Clear to end of line",
+    "Clear to end of display",
+    "Set horizontal position to absolute #1",
+    "Terminal settable cmd characterin prototype",
+    "Move to row #1 col #2",
+    "Down one line",
+    "Home cursor (if no cup)",
+    "Make cursor invisible",
+    "Move left one space.",
+    "Memory relative cursor addressing",
+    "Make cursor appear normal (undo vs/vi)",
+    "Non-destructive space (cursor or carriage right)",
+    "Last line, first column (if no cup)",
+    "Upline (cursor up)",
+    "Make cursor very visible",
+    "Delete character",
+    "Delete line",
+    "Disable status line",
+    "Half-line down (forward 1/2 linefeed)",
+    "Start alternate character set",
+    "Turn on blinking",
+    "Turn on bold (extra bright) mode",
+    "String to begin programs that use cup",
+    "Delete mode (enter)",
+    "Turn on half-bright mode",
+    "Insert mode (enter)",
+    "Turn on blank mode (characters invisible)",
+    "Turn on protected mode",
+    "Turn on reverse video mode",
+    "Begin standout mode",
+    "Start underscore mode",
+    "Erase #1 characters",
+    "End alternate character set",
+    "Turn off all attributes",
+    "String to end programs that use cup",
+    "End delete mode",
+    "End insert mode",
+    "End standout mode",
+    "End underscore mode",
+    "Visible bell (may move cursor)",
+    "Hardcopy terminal page eject",
+    "Return from status line",
+    "Terminal or printer initialisation string",
+    "Terminal or printer initialisation string",
+    "Terminal or printer initialisation string",
+    "Name of initialisation file",
+    "Insert character",
+    "Add new blank line",
+    "Insert pad after character inserted",
+    "sent by backspace key",
+    "sent by clear-all-tabs key",
+    "sent by clear-screen or erase key",
+    "sent by clear-tab key",
+    "sent by delete-character key",
+    "sent by delete-line key",
+    "sent by terminal down-arrow key",
+    "sent by rmir or smir in insert mode",
+    "sent by clear-to-end-of-line key",
+    "sent by clear-to-end-of-screen key",
+    "sent by function key f0",
+    "sent by function key f1",
+    "sent by function key f10",
+    "sent by function key f2",
+    "sent by function key f3",
+    "sent by function key f4",
+    "sent by function key f5",
+    "sent by function key f6",
+    "sent by function key f7",
+    "sent by function key f8",
+    "sent by function key f9",
+    "sent by home key",
+    "sent by ins-char/enter ins-mode key",
+    "sent by insert-line key",
+    "sent by terminal left-arrow key",
+    "sent by home-down key",
+    "sent by next-page key",
+    "sent by previous-page key",
+    "sent by terminal right-arrow key",
+    "sent by scroll-forward/down key",
+    "sent by scroll-backward/up key",
+    "sent by set-tab key",
+    "sent by terminal up-arrow key",
+    "Out of 'keypad-transmit' mode",
+    "Put terminal in 'keypad-transmit' mode",
+    "Labels on function key f0 if not f0",
+    "Labels on function key f1 if not f1",
+    "Labels on function key f10 if not f10",
+    "Labels on function key f2 if not f2",
+    "Labels on function key f3 if not f3",
+    "Labels on function key f4 if not f4",
+    "Labels on function key f5 if not f5",
+    "Labels on function key f6 if not f6",
+    "Labels on function key f7 if not f7",
+    "Labels on function key f8 if not f8",
+    "Labels on function key f9 if not f9",
+    "Turn off 'meta mode'",
+    "Turn on 'meta mode' (8th bit)",
+    "Newline (behaves like cr followed by lf)",
+    "Pad character (rather than null)",
+    "Delete #1 chars",
+    "Delete #1 lines",
+    "Move down #1 lines.",
+    "Insert #1 blank chars",
+    "Scroll forward #1 lines.",
+    "Add #1 new blank lines",
+    "Move cursor left #1 spaces",
+    "Move right #1 spaces.",
+    "Scroll backward #1 lines.",
+    "Move cursor up #1 lines.",
+    "Prog funct key #1 to type string #2",
+    "Prog funct key #1 to execute string #2",
+    "Prog funct key #1 to xmit string #2",
+    "Print contents of the screen",
+    "Turn off the printer",
+    "Turn on the printer",
+    "Repeat char #1 #2 times",
+    "Reset terminal completely to sane modes",
+    "Reset terminal completely to sane modes",
+    "Reset terminal completely to sane modes",
+    "Name of file containing reset string",
+    "Restore cursor to position of last sc",
+    "Set vertical position to absolute #1",
+    "Save cursor position",
+    "Scroll text up",
+    "Scroll text down",
+    "Define first set of video attributes #1-#9",
+    "Set a tab in all rows, current column",
+    "Current window is lines #1-#2 cols #3-#4",
+    "Tab to next 8-space hardware tab stop",
+    "Go to status line, col #1",
+    "Underscore one char and move past it",
+    "Half-line up (reverse 1/2 linefeed)",
+    "Path name of program for initialisation",
+    "upper left of keypad",
+    "upper right of keypad",
+    "center of keypad",
+    "lower left of keypad",
+    "lower right of keypad",
+    "Turn on the printer for #1 bytes",
+    "Like ip but when in replace mode",
+    "Graphic charset pairs aAbBcC",
+    "Prog label #1 to show string #2",
+    "sent by back-tab key",
+    "Turn on xon/xoff handshaking",
+    "Turn off xon/xoff handshaking",
+    "Turn on automatic margins",
+    "Turn off automatic margins",
+    "X-on character",
+    "X-off character",
+    "Enable alternate character set",
+    "Turn on soft labels",
+    "Turn off soft labels",
+    "1",
+    "2",
+    "3",
+    "4",
+    "5",
+    "6",
+    "7",
+    "8",
+    "9",
+    "0",
+    "sent by help key",
+    "sent by mark key",
+    "sent by message key",
+    "sent by move key",
+    "sent by next-object key",
+    "sent by open key",
+    "sent by options key",
+    "sent by previous-object key",
+    "sent by print or copy key",
+    "sent by redo key",
+    "sent by ref(erence) key",
+    "sent by refresh key",
+    "sent by replace key",
+    "sent by restart key",
+    "sent by resume key",
+    "sent by save key",
+    "sent by suspend key",
+    "sent by undo key",
+    "sent by shifted beginning key",
+    "sent by shifted cancel key",
+    "sent by shifted command key",
+    "sent by shifted copy key",
+    "sent by shifted create key",
+    "sent by shifted delete-char key",
+    "sent by shifted delete-line key",
+    "sent by select key",
+    "sent by shifted end key",
+    "sent by shifted clear-line key",
+    "sent by shifted exit key",
+    "sent by shifted find key",
+    "#1  sent by shifted help key",
+    "#2  sent by shifted home key",
+    "#3  sent by shifted input key",
+    "#4  sent by shifted left-arrow key",
+    "sent by shifted message key",
+    "sent by shifted move key",
+    "sent by shifted next key",
+    "sent by shifted options key",
+    "sent by shifted prev key",
+    "sent by shifted print key",
+    "sent by shifted redo key",
+    "sent by shifted replace key",
+    "sent by shifted right-arrow key",
+    "sent by shifted resume key",
+    "!1  sent by shifted save key",
+    "!2  sent by shifted suspend key",
+    "!3  sent by shifted undo key",
+    "Send next input char (for ptys)",
+    "sent by function key f11",
+    "sent by function key f12",
+    "sent by function key f13",
+    "sent by function key f14",
+    "sent by function key f15",
+    "sent by function key f16",
+    "sent by function key f17",
+    "sent by function key f18",
+    "sent by function key f19",
+    "sent by function key f20",
+    "sent by function key f21",
+    "sent by function key f22",
+    "sent by function key f23",
+    "sent by function key f24",
+    "sent by function key f25",
+    "sent by function key f26",
+    "sent by function key f27",
+    "sent by function key f28",
+    "sent by function key f29",
+    "sent by function key f30",
+    "sent by function key f31",
+    "sent by function key f32",
+    "sent by function key f33",
+    "sent by function key f34",
+    "sent by function key f35",
+    "sent by function key f36",
+    "sent by function key f37",
+    "sent by function key f38",
+    "sent by function key f39",
+    "sent by function key f40",
+    "sent by function key f41",
+    "sent by function key f42",
+    "sent by function key f43",
+    "sent by function key f44",
+    "sent by function key f45",
+    "sent by function key f46",
+    "sent by function key f47",
+    "sent by function key f48",
+    "sent by function key f49",
+    "sent by function key f50",
+    "sent by function key f51",
+    "sent by function key f52",
+    "sent by function key f53",
+    "sent by function key f54",
+    "sent by function key f55",
+    "sent by function key f56",
+    "sent by function key f57",
+    "sent by function key f58",
+    "sent by function key f59",
+    "sent by function key f60",
+    "sent by function key f61",
+    "sent by function key f62",
+    "sent by function key f63",
+    "Clear to beginning of line, inclusive",
+    "Clear all margins (top, bottom, and sides)",
+    "Set left margin at current column",
+    "Set right margin at current column",
+    "Label format",
+    "Set clock to hours (#1), minutes (#2), seconds (#3)",
+    "Display time-of-day clock",
+    "Remove time-of-day clock",
+    "Define win #1 to go from #2,#3 to #4,#5",
+    "Go to window #1",
+    "Hang-up phone",
+    "Dial phone number #1",
+    "Dial phone number #1, without progress detection",
+    "Select touch tone dialing",
+    "Select pulse dialing",
+    "Flash the switch hook",
+    "Pause for 2-3 seconds",
+    "Wait for dial tone",
+    "User string 0",
+    "User string 1",
+    "User string 2",
+    "User string 3",
+    "User string 4",
+    "User string 5",
+    "User string 6",
+    "User string 7",
+    "User string 8",
+    "User string 9",
+    "Set default colour-pair to the original one",
+    "Set all colour(-pair)s to the original ones",
+    "Set colour #1 to RGB #2, #3, #4",
+    "Set colour-pair #1 to fg #2, bg #3",
+    "Set current colour pair to #1",
+    "Set foreground colour to #1",
+    "Set background colour to #1",
+    "Change number of characters per inch",
+    "Change number of lines per inch",
+    "Change horizontal resolution",
+    "Change vertical resolution",
+    "Define a character in a character set",
+    "Enable double wide printing",
+    "Set draft quality print",
+    "Enable italics",
+    "Enable leftward carriage motion",
+    "Enable micro motion capabilities",
+    "Set near-letter quality print",
+    "Set normal quality print",
+    "Enable shadow printing",
+    "Enable subscript printing",
+    "Enable superscript printing",
+    "Enable upward carriage motion",
+    "Disable double wide printing",
+    "Disable italics",
+    "Enable rightward (normal) carriage motion",
+    "Disable micro motion capabilities",
+    "Disable shadow printing",
+    "Disable subscript printing",
+    "Disable superscript printing",
+    "Enable downward (normal) carriage motion",
+    "Like columnaddress for micro adjustment",
+    "Like cursordown for micro adjustment",
+    "Like cursorleft for micro adjustment",
+    "Like cursorright for micro adjustment",
+    "Like rowaddress for micro adjustment",
+    "Like cursorup for micro adjustment",
+    "Matches software bits to print-head pins",
+    "Like parmdowncursor for micro adjust.",
+    "Like parmleftcursor for micro adjust.",
+    "Like parmrightcursor for micro adjust.",
+    "Like parmupcursor for micro adjust.",
+    "Select character set",
+    "Set bottom margin at current line",
+    "Set bottom margin at line #1 or #2 lines from bottom",
+    "Set left (right) margin at column #1 (#2)",
+    "Set right margin at column #1",
+    "Set top margin at current line",
+    "Set top (bottom) margin at line #1 (#2)",
+    "Start printing bit image graphics",
+    "Start definition of a character set",
+    "End printing bit image graphics",
+    "End definition of a character set",
+    "List of 'subscript-able' characters",
+    "List of 'superscript-able' characters",
+    "Printing any of these chars causes cr",
+    "No motion for the subsequent character",
+    "Returns a list of character set names",
+    "0631, Mouse event has occured",
+    "Mouse status information",
+    "Request mouse position report",
+    "Curses should get button events",
+    "Set foreground colour to #1 using ANSI escape",
+    "Set background colour to #1 using ANSI escape",
+    "Prog key #1 to xmit string #2 and show string #3",
+    "Indicate language/codeset support",
+    "Init sequence for multiple codesets",
+    "Shift into codeset 0 (EUC set 0, ASCII)",
+    "Shift into codeset 1",
+    "Shift into codeset 2",
+    "Shift into codeset 3",
+    "Sets both left and right margins",
+    "Sets both top and bottom margins",
+    "Repeat bit-image cell #1 #2 times",
+    "Move to next row of the bit image",
+    "Move to beginning of same row",
+    "Give name for colour #1",
+    "Define rectangular bit-image region",
+    "End a bit-image region",
+    "Change to ribbon colour #1",
+    "Set page length to #1 lines",
+    "Display PC character",
+    "Enter PC character display mode",
+    "Disable PC character display mode",
+    "Enter PC scancode mode",
+    "Disable PC scancode mode",
+    "PC terminal options",
+    "Escape for scancode emulation",
+    "Alternate escape for scancode emulation (default is for VT100)",
+    "Turn on horizontal highlight mode",
+    "Turn on left highlight mode",
+    "Turn on low highlight mode",
+    "Turn on right highlight mode",
+    "Turn on top highlight mode",
+    "Turn on vertical highlight mode",
+    "Define second set of video attributes #1-#6",
+    "Set page length to #1 hundredth of an inch",
+];
+
+/// all [`StringCapability`] variants in order
+pub(crate) const STRINGS: [StringCapability; 394] = [
+    StringCapability::BackTab,
+    StringCapability::Bell,
+    StringCapability::CarriageReturn,
+    StringCapability::ChangeScrollRegion,
+    StringCapability::ClearAllTabs,
+    StringCapability::ClearScreen,
+    StringCapability::ClearEOL,
+    StringCapability::ClearEOS,
+    StringCapability::ColumnAddress,
+    StringCapability::CommandCharacter,
+    StringCapability::CursorAddress,
+    StringCapability::CursorDown,
+    StringCapability::CursorHome,
+    StringCapability::CursorInvisible,
+    StringCapability::CursorLeft,
+    StringCapability::CursorMemAddress,
+    StringCapability::CursorNormal,
+    StringCapability::CursorRight,
+    StringCapability::CursorToLastLine,
+    StringCapability::CursorUp,
+    StringCapability::CursorVisible,
+    StringCapability::DeleteCharacter,
+    StringCapability::DeleteLine,
+    StringCapability::DisStatusLine,
+    StringCapability::DownHalfLine,
+    StringCapability::EnterAltCharsetMode,
+    StringCapability::EnterBlinkMode,
+    StringCapability::EnterBoldMode,
+    StringCapability::EnterAlternativeMode,
+    StringCapability::EnterDeleteMode,
+    StringCapability::EnterDimMode,
+    StringCapability::EnterInsertMode,
+    StringCapability::EnterSecureMode,
+    StringCapability::EnterProtectedMode,
+    StringCapability::EnterReverseMode,
+    StringCapability::EnterStandoutMode,
+    StringCapability::EnterUnderlineMode,
+    StringCapability::EraseChars,
+    StringCapability::ExitAltCharsetMode,
+    StringCapability::ExitAttributeMode,
+    StringCapability::ExitAlternativeMode,
+    StringCapability::ExitDeleteMode,
+    StringCapability::ExitInsertMode,
+    StringCapability::ExitStandoutMode,
+    StringCapability::ExitUnderlineMode,
+    StringCapability::FlashScreen,
+    StringCapability::FormFeed,
+    StringCapability::FromStatusLine,
+    StringCapability::Init1String,
+    StringCapability::Init2String,
+    StringCapability::Init3String,
+    StringCapability::InitFile,
+    StringCapability::InsertCharacter,
+    StringCapability::InsertLine,
+    StringCapability::InsertPadding,
+    StringCapability::KeyBackspace,
+    StringCapability::KeyClearAllTabs,
+    StringCapability::KeyClear,
+    StringCapability::KeyClearTab,
+    StringCapability::KeyDeleteCharacter,
+    StringCapability::KeyDeleteLine,
+    StringCapability::KeyDown,
+    StringCapability::KeyEic,
+    StringCapability::KeyClearEOL,
+    StringCapability::KeyClearEOS,
+    StringCapability::KeyF0,
+    StringCapability::KeyF1,
+    StringCapability::KeyF10,
+    StringCapability::KeyF2,
+    StringCapability::KeyF3,
+    StringCapability::KeyF4,
+    StringCapability::KeyF5,
+    StringCapability::KeyF6,
+    StringCapability::KeyF7,
+    StringCapability::KeyF8,
+    StringCapability::KeyF9,
+    StringCapability::KeyHome,
+    StringCapability::KeyInsertCharacter,
+    StringCapability::KeyInsertLine,
+    StringCapability::KeyLeft,
+    StringCapability::KeyLastLine,
+    StringCapability::KeyNextPage,
+    StringCapability::KeyPreviousPage,
+    StringCapability::KeyRight,
+    StringCapability::KeyScrollForward,
+    StringCapability::KeyScrollBackward,
+    StringCapability::KeySetTab,
+    StringCapability::KeyUp,
+    StringCapability::KeypadLocal,
+    StringCapability::KeypadXmit,
+    StringCapability::LabF0,
+    StringCapability::LabF1,
+    StringCapability::LabF10,
+    StringCapability::LabF2,
+    StringCapability::LabF3,
+    StringCapability::LabF4,
+    StringCapability::LabF5,
+    StringCapability::LabF6,
+    StringCapability::LabF7,
+    StringCapability::LabF8,
+    StringCapability::LabF9,
+    StringCapability::MetaOff,
+    StringCapability::MetaOn,
+    StringCapability::Newline,
+    StringCapability::PadChar,
+    StringCapability::ParmDeleteCharacters,
+    StringCapability::ParmDeleteLine,
+    StringCapability::ParmDownCursor,
+    StringCapability::ParmInsertCharacters,
+    StringCapability::ParmIndex,
+    StringCapability::ParmInsertLine,
+    StringCapability::ParmLeftCursor,
+    StringCapability::ParmRightCursor,
+    StringCapability::ParmReverseIndex,
+    StringCapability::ParmUpCursor,
+    StringCapability::PKeyKey,
+    StringCapability::PKeyLocal,
+    StringCapability::PKeyXmit,
+    StringCapability::PrintScreen,
+    StringCapability::PrinterOff,
+    StringCapability::PrinterOn,
+    StringCapability::RepeatChar,
+    StringCapability::Reset1String,
+    StringCapability::Reset2String,
+    StringCapability::Reset3String,
+    StringCapability::ResetFile,
+    StringCapability::RestoreCursor,
+    StringCapability::RowAddress,
+    StringCapability::SaveCursor,
+    StringCapability::ScrollForward,
+    StringCapability::ScrollReverse,
+    StringCapability::SetAttributes,
+    StringCapability::SetTab,
+    StringCapability::SetWindow,
+    StringCapability::Tab,
+    StringCapability::ToStatusLine,
+    StringCapability::UnderlineChar,
+    StringCapability::UpHalfLine,
+    StringCapability::InitProg,
+    StringCapability::KeyA1,
+    StringCapability::KeyA3,
+    StringCapability::KeyB2,
+    StringCapability::KeyC1,
+    StringCapability::KeyC3,
+    StringCapability::PrinterOnForNBytes,
+    StringCapability::CharPadding,
+    StringCapability::AcsChars,
+    StringCapability::PlabNorm,
+    StringCapability::KeyBackTab,
+    StringCapability::EnterXonMode,
+    StringCapability::ExitXonMode,
+    StringCapability::EnterAutomaticMarginsMode,
+    StringCapability::ExitAutomaticMarginsMode,
+    StringCapability::XOnCharacter,
+    StringCapability::XOffCharacter,
+    StringCapability::EnableAlternateCharSet,
+    StringCapability::LabelOn,
+    StringCapability::LabelOff,
+    StringCapability::KeyBegin,
+    StringCapability::KeyCancel,
+    StringCapability::KeyClose,
+    StringCapability::KeyCommand,
+    StringCapability::KeyCopy,
+    StringCapability::KeyCreate,
+    StringCapability::KeyEnd,
+    StringCapability::KeyEnter,
+    StringCapability::KeyExit,
+    StringCapability::KeyFind,
+    StringCapability::KeyHelp,
+    StringCapability::KeyMark,
+    StringCapability::KeyMessage,
+    StringCapability::KeyMove,
+    StringCapability::KeyNext,
+    StringCapability::KeyOpen,
+    StringCapability::KeyOptions,
+    StringCapability::KeyPrevious,
+    StringCapability::KeyPrint,
+    StringCapability::KeyRedo,
+    StringCapability::KeyReference,
+    StringCapability::KeyRefresh,
+    StringCapability::KeyReplace,
+    StringCapability::KeyRestart,
+    StringCapability::KeyResume,
+    StringCapability::KeySave,
+    StringCapability::KeySuspend,
+    StringCapability::KeyUndo,
+    StringCapability::KeyShiftBegin,
+    StringCapability::KeyShiftCancel,
+    StringCapability::KeyShiftCommand,
+    StringCapability::KeyShiftCopy,
+    StringCapability::KeyShiftCreate,
+    StringCapability::KeyShiftDeleteChar,
+    StringCapability::KeyShiftDeleteLine,
+    StringCapability::KeySelect,
+    StringCapability::KeyShiftEnd,
+    StringCapability::KeyShiftEOL,
+    StringCapability::KeyShiftExit,
+    StringCapability::KeyShiftFind,
+    StringCapability::KeyShiftHelp,
+    StringCapability::KeyShiftHome,
+    StringCapability::KeyShiftInputKey,
+    StringCapability::KeyShiftLeft,
+    StringCapability::KeyShiftMessage,
+    StringCapability::KeyShiftMove,
+    StringCapability::KeyShiftNext,
+    StringCapability::KeyShiftOptions,
+    StringCapability::KeyShiftPrevious,
+    StringCapability::KeyShiftPrint,
+    StringCapability::KeyShiftRedo,
+    StringCapability::KeyShiftReplace,
+    StringCapability::KeyShiftRight,
+    StringCapability::KeyShiftResume,
+    StringCapability::KeyShiftSave,
+    StringCapability::KeyShiftSuspend,
+    StringCapability::KeyShiftUndo,
+    StringCapability::ReqForInput,
+    StringCapability::KeyF11,
+    StringCapability::KeyF12,
+    StringCapability::KeyF13,
+    StringCapability::KeyF14,
+    StringCapability::KeyF15,
+    StringCapability::KeyF16,
+    StringCapability::KeyF17,
+    StringCapability::KeyF18,
+    StringCapability::KeyF19,
+    StringCapability::KeyF20,
+    StringCapability::KeyF21,
+    StringCapability::KeyF22,
+    StringCapability::KeyF23,
+    StringCapability::KeyF24,
+    StringCapability::KeyF25,
+    StringCapability::KeyF26,
+    StringCapability::KeyF27,
+    StringCapability::KeyF28,
+    StringCapability::KeyF29,
+    StringCapability::KeyF30,
+    StringCapability::KeyF31,
+    StringCapability::KeyF32,
+    StringCapability::KeyF33,
+    StringCapability::KeyF34,
+    StringCapability::KeyF35,
+    StringCapability::KeyF36,
+    StringCapability::KeyF37,
+    StringCapability::KeyF38,
+    StringCapability::KeyF39,
+    StringCapability::KeyF40,
+    StringCapability::KeyF41,
+    StringCapability::KeyF42,
+    StringCapability::KeyF43,
+    StringCapability::KeyF44,
+    StringCapability::KeyF45,
+    StringCapability::KeyF46,
+    StringCapability::KeyF47,
+    StringCapability::KeyF48,
+    StringCapability::KeyF49,
+    StringCapability::KeyF50,
+    StringCapability::KeyF51,
+    StringCapability::KeyF52,
+    StringCapability::KeyF53,
+    StringCapability::KeyF54,
+    StringCapability::KeyF55,
+    StringCapability::KeyF56,
+    StringCapability::KeyF57,
+    StringCapability::KeyF58,
+    StringCapability::KeyF59,
+    StringCapability::KeyF60,
+    StringCapability::KeyF61,
+    StringCapability::KeyF62,
+    StringCapability::KeyF63,
+    StringCapability::ClearBOL,
+    StringCapability::ClearMargins,
+    StringCapability::SetLeftMargin,
+    StringCapability::SetRightMargin,
+    StringCapability::LabelFormat,
+    StringCapability::SetClock,
+    StringCapability::DisplayClock,
+    StringCapability::RemoveClock,
+    StringCapability::CreateWindow,
+    StringCapability::GotoWindow,
+    StringCapability::Hangup,
+    StringCapability::DialPhone,
+    StringCapability::QuickDial,
+    StringCapability::Tone,
+    StringCapability::Pulse,
+    StringCapability::FlashHook,
+    StringCapability::FixedPause,
+    StringCapability::WaitTone,
+    StringCapability::User0,
+    StringCapability::User1,
+    StringCapability::User2,
+    StringCapability::User3,
+    StringCapability::User4,
+    StringCapability::User5,
+    StringCapability::User6,
+    StringCapability::User7,
+    StringCapability::User8,
+    StringCapability::User9,
+    StringCapability::OrigColorPair,
+    StringCapability::OrigColors,
+    StringCapability::InitializeColor,
+    StringCapability::InitializePair,
+    StringCapability::SetColorPair,
+    StringCapability::SetForeground,
+    StringCapability::SetBackground,
+    StringCapability::ChangeCharPitch,
+    StringCapability::ChangeLinePitch,
+    StringCapability::ChangeResHorz,
+    StringCapability::ChangeResVert,
+    StringCapability::DefineChar,
+    StringCapability::EnterDoublewideMode,
+    StringCapability::EnterDraftQuality,
+    StringCapability::EnterItalicsMode,
+    StringCapability::EnterLeftwardMode,
+    StringCapability::EnterMicroMode,
+    StringCapability::EnterNearLetterQuality,
+    StringCapability::EnterNormalQuality,
+    StringCapability::EnterShadowMode,
+    StringCapability::EnterSubscriptMode,
+    StringCapability::EnterSuperscriptMode,
+    StringCapability::EnterUpwardMode,
+    StringCapability::ExitDoublewideMode,
+    StringCapability::ExitItalicsMode,
+    StringCapability::ExitLeftwardMode,
+    StringCapability::ExitMicroMode,
+    StringCapability::ExitShadowMode,
+    StringCapability::ExitSubscriptMode,
+    StringCapability::ExitSuperscriptMode,
+    StringCapability::ExitUpwardMode,
+    StringCapability::MicroColumnAddress,
+    StringCapability::MicroDown,
+    StringCapability::MicroLeft,
+    StringCapability::MicroRight,
+    StringCapability::MicroRowAddress,
+    StringCapability::MicroUp,
+    StringCapability::OrderOfPins,
+    StringCapability::ParmDownMicro,
+    StringCapability::ParmLeftMicro,
+    StringCapability::ParmRightMicro,
+    StringCapability::ParmUpMicro,
+    StringCapability::SelectCharSet,
+    StringCapability::SetBottomMargin,
+    StringCapability::SetBottomMarginParm,
+    StringCapability::SetLeftMarginParm,
+    StringCapability::SetRightMarginParm,
+    StringCapability::SetTopMargin,
+    StringCapability::SetTopMarginParm,
+    StringCapability::StartBitImage,
+    StringCapability::StartCharSetDef,
+    StringCapability::StopBitImage,
+    StringCapability::StopCharSetDef,
+    StringCapability::SubscriptCharacters,
+    StringCapability::SuperscriptCharacters,
+    StringCapability::TheseCauseCr,
+    StringCapability::ZeroMotion,
+    StringCapability::CharSetNames,
+    StringCapability::KeyMouse,
+    StringCapability::MouseInfo,
+    StringCapability::ReqMousePos,
+    StringCapability::GetMouse,
+    StringCapability::SetAnsiForeground,
+    StringCapability::SetAnsiBackground,
+    StringCapability::PKeyPlab,
+    StringCapability::DeviceType,
+    StringCapability::CodeSetInit,
+    StringCapability::Set0DesSeq,
+    StringCapability::Set1DesSeq,
+    StringCapability::Set2DesSeq,
+    StringCapability::Set3DesSeq,
+    StringCapability::SetLrMargin,
+    StringCapability::SetTbMargin,
+    StringCapability::BitImageRepeat,
+    StringCapability::BitImageNewline,
+    StringCapability::BitImageCarriageReturn,
+    StringCapability::ColorNames,
+    StringCapability::DefineBitImageRegion,
+    StringCapability::EndBitImageRegion,
+    StringCapability::SetColorBand,
+    StringCapability::SetPageLength,
+    StringCapability::DisplayPcChar,
+    StringCapability::EnterPcCharsetMode,
+    StringCapability::ExitPcCharsetMode,
+    StringCapability::EnterScancodeMode,
+    StringCapability::ExitScancodeMode,
+    StringCapability::PcTermOptions,
+    StringCapability::ScancodeEscape,
+    StringCapability::AltScancodeEsc,
+    StringCapability::EnterHorizontalHlMode,
+    StringCapability::EnterLeftHlMode,
+    StringCapability::EnterLowHlMode,
+    StringCapability::EnterRightHlMode,
+    StringCapability::EnterTopHlMode,
+    StringCapability::EnterVerticalHlMode,
+    StringCapability::SetAAttributes,
+    StringCapability::SetPageLenInch,
+];
@@ -0,0 +1,120 @@
+//! Comparison of two [`TermInfo`] entries, like `infocmp -d`
+
+use std::fmt;
+
+use crate::{BoolCapability, NumberCapability, StringCapability, TermInfo, Value};
+
+/// A standard or extended capability
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Hash, PartialOrd, Ord)]
+pub enum Capability<'a> {
+    Bool(BoolCapability),
+    Number(NumberCapability),
+    String(StringCapability),
+    Extended(&'a str),
+}
+
+impl<'a> Capability<'a> {
+    /// Returns the terminfo name of the capability
+    pub fn name(self) -> &'a str {
+        match self {
+            Capability::Bool(cap) => cap.name(),
+            Capability::Number(cap) => cap.name(),
+            Capability::String(cap) => cap.name(),
+            Capability::Extended(name) => name,
+        }
+    }
+}
+
+impl fmt::Display for Capability<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// The state of a capability in an entry
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum State<'a> {
+    /// not present in the entry
+    Absent,
+    /// explicitly removed from the entry (`cap@`)
+    Cancelled,
+    Present(Value<'a>),
+}
+
+/// A capability that differs between two entries, see [`TermInfo::diff`]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Difference<'a> {
+    pub capability: Capability<'a>,
+    /// the state in the entry `diff` was called on
+    pub left: State<'a>,
+    /// the state in the entry passed to `diff`
+    pub right: State<'a>,
+}
+
+impl TermInfo {
+    /// Returns all capabilities that differ between `self` and `other`, including
+    /// capabilities that are only present or cancelled in one of the entries.
+    /// Standard capabilities are returned in the order they are stored in compiled
+    /// entries (bools, numbers, strings), followed by the extended capabilities sorted by name.
+    ///
+    /// # Example
+    /// ```
+    /// use termini::TermInfo;
+    ///
+    /// let kitty = TermInfo::from_name("xterm-kitty");
+    /// let tmux = TermInfo::from_name("tmux-256color");
+    /// if let (Ok(kitty), Ok(tmux)) = (kitty, tmux) {
+    ///     for diff in kitty.diff(&tmux) {
+    ///         println!("{}: {:?} != {:?}", diff.capability, diff.left, diff.right);
+    ///     }
+    /// }
+    /// ```
+    pub fn diff<'a>(&'a self, other: &'a TermInfo) -> Vec<Difference<'a>> {
+        let mut res = Vec::new();
+        let mut push = |capability, left, right| {
+            if left != right {
+                res.push(Difference {
+                    capability,
+                    left,
+                    right,
+                })
+            }
+        };
+        for &cap in BoolCapability::ALL {
+            push(
+                Capability::Bool(cap),
+                self.bool_state(cap),
+                other.bool_state(cap),
+            );
+        }
+        for &cap in NumberCapability::ALL {
+            push(
+                Capability::Number(cap),
+                self.number_cap_state(cap),
+                other.number_cap_state(cap),
+            );
+        }
+        for &cap in StringCapability::ALL {
+            push(
+                Capability::String(cap),
+                self.string_cap_state(cap),
+                other.string_cap_state(cap),
+            );
+        }
+
+        let mut names: Vec<&str> = (self.extended.capabilities.keys())
+            .chain(other.extended.capabilities.keys())
+            .map(String::as_str)
+            .collect();
+        names.sort_unstable();
+        names.dedup();
+        for name in names {
+            push(
+                Capability::Extended(name),
+                self.extended_cap_state(name),
+                other.extended_cap_state(name),
+            );
+        }
+        res
+    }
+}
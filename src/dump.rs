@@ -0,0 +1,231 @@
+//! Rendering of [`TermInfo`] as terminfo source text, like `infocmp`
+
+use std::fmt;
+
+use crate::capabilities::{BOOL_NAMES, NUMBER_NAMES, STRING_NAMES};
+use crate::source::{CANCELLED_NUMBER, CANCELLED_STRING};
+use crate::{State, TermInfo, Value};
+
+/// Order of the capabilities within each type when dumping a [`TermInfo`]
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum SortOrder {
+    /// Sorted by short name, like `infocmp -si` (the default of `infocmp`)
+    #[default]
+    Name,
+    /// The order the capabilities are stored in compiled entries, like `infocmp -sd`
+    Database,
+}
+
+/// Options for [`TermInfo::dump`]
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct DumpOptions {
+    /// order of the capabilities within each type
+    pub sort: SortOrder,
+    /// maximum line width, capabilities that are too long get a line of their own.
+    /// A width of 0 prints one capability per line like `infocmp -1`
+    pub width: usize,
+    /// include extended capabilities like `infocmp -x`
+    pub extended: bool,
+}
+
+impl Default for DumpOptions {
+    fn default() -> DumpOptions {
+        DumpOptions {
+            sort: SortOrder::Name,
+            width: 60,
+            extended: true,
+        }
+    }
+}
+
+/// Terminfo source text of a [`TermInfo`], created by [`TermInfo::dump`]
+#[derive(Debug, Clone, Copy)]
+pub struct Dump<'a> {
+    info: &'a TermInfo,
+    options: DumpOptions,
+}
+
+impl TermInfo {
+    /// Renders the entry as terminfo source text that can be compiled with `tic` or
+    /// [`TermInfo::from_source`]. The output matches `infocmp -x` (with the same
+    /// options) except for the leading comment.
+    ///
+    /// # Example
+    /// ```
+    /// use termini::{DumpOptions, TermInfo};
+    ///
+    /// if let Ok(info) = TermInfo::from_env() {
+    ///     let one_per_line = DumpOptions {
+    ///         width: 0,
+    ///         ..DumpOptions::default()
+    ///     };
+    ///     println!("{}", info.dump(one_per_line));
+    /// }
+    /// ```
+    pub fn dump(&self, options: DumpOptions) -> Dump<'_> {
+        Dump {
+            info: self,
+            options,
+        }
+    }
+}
+
+/// tab stop of continuation lines
+const INDENT: usize = 8;
+
+/// Writes the capabilities of one type separated by `, ` and wraps lines like `infocmp`
+fn write_group(f: &mut fmt::Formatter<'_>, caps: &[(&str, String)], width: usize) -> fmt::Result {
+    if caps.is_empty() {
+        return Ok(());
+    }
+    f.write_str("\t")?;
+    let mut column = INDENT;
+    for (_, cap) in caps {
+        // the separator is not counted towards the column by infocmp
+        if column > INDENT && column + cap.len() + 2 > width {
+            f.write_str("\n\t")?;
+            column = INDENT;
+        } else if column > INDENT {
+            f.write_str(" ")?;
+        }
+        write!(f, "{cap},")?;
+        column += cap.len();
+    }
+    f.write_str("\n")
+}
+
+impl fmt::Display for Dump<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let info = self.info;
+        writeln!(f, "{},", info.names_section())?;
+
+        let sort = |caps: &mut Vec<(&str, String)>| {
+            if self.options.sort == SortOrder::Name {
+                caps.sort_by_key(|&(name, _)| name);
+            }
+        };
+
+        let mut bools: Vec<_> = (info.data.bools.iter().zip(BOOL_NAMES))
+            .filter(|(&val, _)| val)
+            .map(|(_, name)| (name, name.to_owned()))
+            .collect();
+        let mut numbers: Vec<_> = (info.data.numbers.iter().zip(NUMBER_NAMES))
+            .filter(|(&val, _)| val >= 0 || val == CANCELLED_NUMBER)
+            .map(|(&val, name)| (name, number(name, val)))
+            .collect();
+        let mut strings: Vec<_> = (info.data.strings.iter().zip(STRING_NAMES))
+            .filter_map(|(&off, name)| {
+                if off == CANCELLED_STRING {
+                    return Some((name, format!("{name}@")));
+                }
+                let val = info.data.get_str_at(off)?;
+                if name == "acsc" {
+                    return Some((name, format!("{name}={}", escape(&sort_acsc(val)))));
+                }
+                Some((name, format!("{name}={}", escape(val))))
+            })
+            .collect();
+        sort(&mut bools);
+        sort(&mut numbers);
+        sort(&mut strings);
+
+        // extended capabilities follow the standard capabilities sorted by name
+        if self.options.extended {
+            for name in info.extended.capabilities.keys() {
+                let name = name.as_str();
+                match info.extended_cap_state(name) {
+                    State::Present(Value::True) => bools.push((name, name.to_owned())),
+                    State::Present(Value::Number(val)) => numbers.push((name, number(name, val))),
+                    State::Present(Value::RawString(val)) => {
+                        strings.push((name, format!("{name}={}", escape(val))))
+                    }
+                    State::Present(Value::Utf8String(val)) => {
+                        strings.push((name, format!("{name}={}", escape(val.as_bytes()))))
+                    }
+                    // cancelled capabilities of all types are stored as strings
+                    State::Cancelled => strings.push((name, format!("{name}@"))),
+                    State::Absent => (),
+                }
+            }
+        }
+
+        for caps in [bools, numbers, strings] {
+            write_group(f, &caps, self.options.width)?;
+        }
+        Ok(())
+    }
+}
+
+/// Formats a number capability, like infocmp numbers close to a power of two are
+/// written in hex
+fn number(name: &str, val: i32) -> String {
+    if val == CANCELLED_NUMBER {
+        return format!("{name}@");
+    }
+    // computed as u32 as `val + 1` overflows for `i32::MAX`
+    let pow2 = |val: u32| val & (val - 1) == 0;
+    if val > 255 && (pow2(val as u32) || pow2(val as u32 + 1)) {
+        format!("{name}#{val:#x}")
+    } else {
+        format!("{name}#{val}")
+    }
+}
+
+/// Sorts the character pairs of `acsc` by the VT100 character like infocmp,
+/// later pairs replace earlier pairs for the same character
+fn sort_acsc(val: &[u8]) -> Vec<u8> {
+    if val.len() % 2 == 1 {
+        return val.to_vec();
+    }
+    let mut mapping = [None; 256];
+    for pair in val.chunks(2) {
+        mapping[usize::from(pair[0])] = Some(pair[1]);
+    }
+    let pairs = mapping.iter().enumerate();
+    pairs
+        .filter_map(|(c, mapped)| Some([c as u8, (*mapped)?]))
+        .flatten()
+        .collect()
+}
+
+/// Escapes a string value for terminfo source, like `_nc_tic_expand` from ncurses
+fn escape(val: &[u8]) -> String {
+    let mut res = String::with_capacity(val.len());
+    // short strings always use the `^X` notation for control characters
+    let long = val.len() > 3;
+    let mut i = 0;
+    while let Some(&c) = val.get(i) {
+        let next = val.get(i + 1).copied();
+        match c {
+            // the character following a `%` is never escaped
+            b'%' if next.is_some_and(is_print) => {
+                res.push('%');
+                res.push(next.unwrap().into());
+                i += 1;
+            }
+            0o200 => res.push_str("\\0"),
+            0o33 => res.push_str("\\E"),
+            b'\\' if i == 0 || val[i - 1] != b'^' => res.push_str("\\\\"),
+            b' ' if i == 0 || val[i..].iter().all(|&c| c == b' ') => res.push_str("\\s"),
+            b',' | b'^' => {
+                res.push('\\');
+                res.push(c.into());
+            }
+            _ if is_print(c) => res.push(c.into()),
+            b'\r' => res.push_str("\\r"),
+            b'\n' => res.push_str("\\n"),
+            0o177 => res.push_str("^?"),
+            _ if c < 0o40 && (!long || next.is_some_and(|c| c.is_ascii_digit())) => {
+                res.push('^');
+                res.push((c + b'@').into());
+            }
+            _ => res.push_str(&format!("\\{c:03o}")),
+        }
+        i += 1;
+    }
+    res
+}
+
+fn is_print(c: u8) -> bool {
+    matches!(c, b' '..=b'~')
+}
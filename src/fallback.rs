@@ -0,0 +1,191 @@
+//! Falling back to more generic entries for terminal types that aren't installed,
+//! see [`Fallbacks`]
+
+use crate::{Error, NotFound, SearchPath, TermInfo};
+
+/// Rules to derive more generic terminal names from a name that isn't installed,
+/// for example when the `TERM` of a remote client is missing on a minimal system.
+///
+/// Starting with the requested name, each name is followed by the first rule that
+/// applies to it:
+///
+/// 1. the fallback of the name added with [`Fallbacks::add_name`]
+/// 2. the name with its prefix replaced, added with [`Fallbacks::add_prefix`]
+/// 3. the name without its last `-` or `.` suffix (`screen-256color` → `screen`)
+///    unless disabled with [`Fallbacks::strip_suffixes`]
+///
+/// until no rule applies, followed by the last resorts added with
+/// [`Fallbacks::add_last_resort`].
+///
+/// [`Fallbacks::default`] contains rules for common terminals:
+/// `tmux-256color` falls back to `screen-256color`, `screen`, `ansi` and `dumb`
+/// while `xterm-kitty` falls back to `xterm-256color`, `xterm`, `ansi` and `dumb`.
+///
+/// # Example
+/// ```
+/// use termini::{Fallbacks, TermInfo};
+///
+/// let mut fallbacks = Fallbacks::default();
+/// fallbacks.add_name("myterm", "xterm-256color");
+/// if let Ok(resolved) = TermInfo::from_env_with_fallback(&fallbacks) {
+///     if let Some(fallback) = &resolved.fallback {
+///         eprintln!("{} is not installed, using {fallback}", resolved.requested);
+///     }
+/// }
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Fallbacks {
+    names: Vec<(String, String)>,
+    prefixes: Vec<(String, String)>,
+    strip_suffixes: bool,
+    last_resorts: Vec<String>,
+}
+
+impl Fallbacks {
+    /// Creates rules that only strip suffixes
+    pub fn new() -> Fallbacks {
+        Fallbacks {
+            names: Vec::new(),
+            prefixes: Vec::new(),
+            strip_suffixes: true,
+            last_resorts: Vec::new(),
+        }
+    }
+
+    /// Falls back to `fallback` for the name `name`. Rules added first take precedence.
+    pub fn add_name(&mut self, name: impl Into<String>, fallback: impl Into<String>) {
+        self.names.push((name.into(), fallback.into()));
+    }
+
+    /// Replaces `prefix` with `replacement` for names that start with `prefix`, so
+    /// `add_prefix("tmux", "screen")` falls back to `screen-256color` for
+    /// `tmux-256color`. Rules added first take precedence.
+    pub fn add_prefix(&mut self, prefix: impl Into<String>, replacement: impl Into<String>) {
+        self.prefixes.push((prefix.into(), replacement.into()));
+    }
+
+    /// Sets whether names fall back to the name without their last suffix
+    pub fn strip_suffixes(&mut self, strip: bool) {
+        self.strip_suffixes = strip;
+    }
+
+    /// Adds `name` to the names that are tried after all other fallbacks
+    pub fn add_last_resort(&mut self, name: impl Into<String>) {
+        self.last_resorts.push(name.into());
+    }
+
+    /// Returns the names that are tried for `name` in order, starting with `name`
+    ///
+    /// # Example
+    /// ```
+    /// use termini::Fallbacks;
+    ///
+    /// assert_eq!(
+    ///     Fallbacks::default().names("tmux-256color"),
+    ///     ["tmux-256color", "screen-256color", "screen", "ansi", "dumb"]
+    /// );
+    /// ```
+    pub fn names(&self, name: &str) -> Vec<String> {
+        let mut res: Vec<String> = Vec::new();
+        let mut next = Some(name.to_owned()).filter(|name| !name.is_empty());
+        // stops at cycles in the rules
+        while let Some(name) = next.filter(|name| !res.contains(name)) {
+            next = self.fallback(&name);
+            res.push(name);
+        }
+        for name in &self.last_resorts {
+            if !res.contains(name) {
+                res.push(name.clone());
+            }
+        }
+        res
+    }
+
+    fn fallback(&self, name: &str) -> Option<String> {
+        if let Some((_, fallback)) = self.names.iter().find(|(from, _)| from == name) {
+            return Some(fallback.clone());
+        }
+        for (prefix, replacement) in &self.prefixes {
+            if let Some(rest) = name.strip_prefix(&**prefix) {
+                return Some(format!("{replacement}{rest}"));
+            }
+        }
+        if self.strip_suffixes {
+            let end = name.rfind(['-', '.']).filter(|&end| end > 0)?;
+            return Some(name[..end].to_owned());
+        }
+        None
+    }
+
+    /// Loads the first entry of [`Fallbacks::names`] for `name` that exists on
+    /// `search`. Names that aren't valid terminal names are skipped.
+    ///
+    /// If none of them exist, the error for `name` itself is returned
+    /// ([`Error::NotFound`] or [`Error::InvalidTermName`]).
+    pub fn resolve(&self, search: &SearchPath, name: &str) -> Result<Resolved, Error> {
+        let mut not_found = None;
+        for candidate in self.names(name) {
+            match TermInfo::from_name_in(search, &candidate) {
+                Ok(info) => {
+                    return Ok(Resolved {
+                        info,
+                        requested: name.to_owned(),
+                        fallback: (candidate != name).then_some(candidate),
+                    })
+                }
+                Err(err @ (Error::NotFound(_) | Error::InvalidTermName(_))) => {
+                    if candidate == name {
+                        not_found = Some(err);
+                    }
+                }
+                Err(err) => return Err(err),
+            }
+        }
+        Err(not_found.unwrap_or_else(|| NotFound::new(name).into()))
+    }
+}
+
+impl Default for Fallbacks {
+    fn default() -> Fallbacks {
+        let mut res = Fallbacks::new();
+        for name in [
+            "xterm-kitty",
+            "xterm-ghostty",
+            "alacritty",
+            "foot",
+            "wezterm",
+        ] {
+            res.add_name(name, "xterm-256color");
+        }
+        res.add_prefix("tmux", "screen");
+        res.add_last_resort("ansi");
+        res.add_last_resort("dumb");
+        res
+    }
+}
+
+/// An entry loaded by [`Fallbacks::resolve`]
+#[derive(Debug)]
+pub struct Resolved {
+    pub info: TermInfo,
+    /// the name that was requested
+    pub requested: String,
+    /// the name the entry was loaded for if the requested entry is missing
+    pub fallback: Option<String>,
+}
+
+impl TermInfo {
+    /// Create TermInfo database using the TERM environment var like
+    /// [`TermInfo::from_env`], falling back to more generic entries if it isn't
+    /// installed (or TERM isn't set), see [`Fallbacks`]
+    pub fn from_env_with_fallback(fallbacks: &Fallbacks) -> Result<Resolved, Error> {
+        let term = std::env::var("TERM").unwrap_or_default();
+        TermInfo::from_name_with_fallback(&term, fallbacks)
+    }
+
+    /// Create TermInfo database for the given name like [`TermInfo::from_name`],
+    /// falling back to more generic entries if it isn't installed, see [`Fallbacks`]
+    pub fn from_name_with_fallback(name: &str, fallbacks: &Fallbacks) -> Result<Resolved, Error> {
+        fallbacks.resolve(&SearchPath::from_env(), name)
+    }
+}
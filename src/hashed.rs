@@ -0,0 +1,351 @@
+//! Reading entries from hashed terminfo databases (`terminfo.db`), which ncurses
+//! builds configured with `--with-hashed-db` use instead of a directory tree.
+//!
+//! The database is a Berkeley DB hash database that maps the name of each entry to
+//! a 0 byte followed by the compiled entry and each alias to a 2 byte followed by
+//! the name of the entry.
+
+use std::collections::{HashMap, HashSet};
+use std::fs::File;
+use std::io::{Read, Seek, SeekFrom};
+use std::ops::RangeInclusive;
+use std::path::Path;
+
+use crate::parsing::MAX_ENTRY_SIZE;
+use crate::Error;
+
+const HASH_MAGIC: u32 = 0x061561;
+/// hash database versions of Berkeley DB 4.x to 6.x, older versions use another layout
+const HASH_VERSIONS: RangeInclusive<u32> = 8..=10;
+
+// page types
+const P_HASH_UNSORTED: u8 = 2;
+const P_OVERFLOW: u8 = 7;
+const P_HASHMETA: u8 = 8;
+const P_HASH: u8 = 13;
+
+// item types on hash pages
+const H_KEYDATA: u8 = 1;
+const H_OFFPAGE: u8 = 3;
+
+/// size of the header of every page
+const PAGE_HEADER: usize = 26;
+/// hashed by Berkeley DB to detect databases that use a custom hash function
+const CHARKEY: &[u8] = b"%$sniglet^&\0";
+
+/// prefix of the data of entries
+const ENTRY: u8 = 0;
+/// prefix of the data of aliases
+const ALIAS: u8 = 2;
+
+/// The default hash function of Berkeley DB (`__ham_func5`)
+fn hash(key: &[u8]) -> u32 {
+    key.iter().fold(0, |hash: u32, &c| {
+        hash.wrapping_mul(16777619) ^ u32::from(c)
+    })
+}
+
+/// A key or value stored on a hash page
+enum Item {
+    Inline(Vec<u8>),
+    /// stored on a chain of overflow pages
+    Overflow {
+        page: u32,
+        len: u32,
+    },
+}
+
+/// A Berkeley DB hash database opened for reading
+pub(crate) struct HashedDb<R> {
+    reader: R,
+    big_endian: bool,
+    page_size: usize,
+    last_page: u32,
+    max_bucket: u32,
+    high_mask: u32,
+    low_mask: u32,
+    /// used to find the first page of a bucket
+    spares: [u32; 32],
+    /// whether keys can be looked up by their hash, otherwise all buckets are searched
+    default_hash: bool,
+}
+
+impl HashedDb<File> {
+    pub(crate) fn open(path: &Path) -> Result<HashedDb<File>, Error> {
+        HashedDb::new(File::open(path)?)
+    }
+}
+
+impl<R: Read + Seek> HashedDb<R> {
+    pub(crate) fn new(mut reader: R) -> Result<HashedDb<R>, Error> {
+        // the metadata page is at least 512 bytes (the minimum page size)
+        let mut meta = [0; 512];
+        reader.read_exact(&mut meta)?;
+        let big_endian = match meta[12..16].try_into().unwrap() {
+            magic if u32::from_le_bytes(magic) == HASH_MAGIC => false,
+            magic if u32::from_be_bytes(magic) == HASH_MAGIC => true,
+            _ => return Err(Error::InvalidHashedDb),
+        };
+        let mut db = HashedDb {
+            reader,
+            big_endian,
+            page_size: 0,
+            last_page: 0,
+            max_bucket: 0,
+            high_mask: 0,
+            low_mask: 0,
+            spares: [0; 32],
+            default_hash: false,
+        };
+        let version = db.u32_at(&meta, 16)?;
+        let page_size = db.u32_at(&meta, 20)?;
+        let (encrypted, page_type, checksum) = (meta[24] != 0, meta[25], meta[26] & 1 != 0);
+        if !HASH_VERSIONS.contains(&version)
+            || !(512..=65536).contains(&page_size)
+            || !page_size.is_power_of_two()
+            || page_type != P_HASHMETA
+            // the layout of pages differs for encrypted databases and databases with checksums
+            || encrypted
+            || checksum
+        {
+            return Err(Error::InvalidHashedDb);
+        }
+        db.page_size = page_size as usize;
+        // the header can't be trusted to limit the number of pages that are read
+        let pages = db.reader.seek(SeekFrom::End(0))? / u64::from(page_size);
+        let last_page = u64::from(db.u32_at(&meta, 32)?).min(pages.saturating_sub(1));
+        db.last_page = last_page as u32;
+        db.max_bucket = db.u32_at(&meta, 72)?;
+        db.high_mask = db.u32_at(&meta, 76)?;
+        db.low_mask = db.u32_at(&meta, 80)?;
+        db.default_hash = db.u32_at(&meta, 92)? == hash(CHARKEY);
+        for i in 0..db.spares.len() {
+            db.spares[i] = db.u32_at(&meta, 96 + 4 * i)?;
+        }
+        // every bucket has at least one page
+        if db.max_bucket > db.last_page {
+            return Err(Error::InvalidHashedDb);
+        }
+        Ok(db)
+    }
+
+    /// Returns the compiled entry `name`. Aliases are resolved to the compiled entry
+    /// of the name they refer to.
+    pub(crate) fn entry(&mut self, name: &str) -> Result<Option<Vec<u8>>, Error> {
+        let Some(mut data) = self.get(name.as_bytes())? else {
+            return Ok(None);
+        };
+        if data.first() == Some(&ALIAS) {
+            let name = data[1..].split(|&c| c == b'\0').next().unwrap();
+            match self.get(name)? {
+                Some(entry) => data = entry,
+                None => return Ok(None),
+            }
+        }
+        if data.first() != Some(&ENTRY) {
+            return Err(Error::InvalidHashedDb);
+        }
+        data.remove(0);
+        Ok(Some(data))
+    }
+
+    /// Returns the value stored for `key`
+    pub(crate) fn get(&mut self, key: &[u8]) -> Result<Option<Vec<u8>>, Error> {
+        let buckets = if self.default_hash {
+            let mut bucket = hash(key) & self.high_mask;
+            if bucket > self.max_bucket {
+                bucket &= self.low_mask;
+            }
+            if bucket > self.max_bucket {
+                return Err(Error::InvalidHashedDb);
+            }
+            bucket..=bucket
+        } else {
+            0..=self.max_bucket
+        };
+        let mut visited = HashSet::new();
+        for bucket in buckets {
+            for (item_key, val) in self.bucket(bucket, &mut visited)? {
+                if self.item_eq(item_key, key)? {
+                    return self.item_data(val).map(Some);
+                }
+            }
+        }
+        Ok(None)
+    }
+
+    /// Returns the names of all entries and aliases
+    pub(crate) fn names(&mut self) -> Result<Vec<String>, Error> {
+        let mut res = Vec::new();
+        let mut visited = HashSet::new();
+        for bucket in 0..=self.max_bucket {
+            for (key, _) in self.bucket(bucket, &mut visited)? {
+                if let Ok(name) = String::from_utf8(self.item_data(key)?) {
+                    res.push(name);
+                }
+            }
+        }
+        Ok(res)
+    }
+
+    /// Returns the names of all entries and aliases with the compiled entry they refer to
+    pub(crate) fn entries(&mut self) -> Result<Vec<(String, Vec<u8>)>, Error> {
+        let mut pairs = Vec::new();
+        let mut visited = HashSet::new();
+        for bucket in 0..=self.max_bucket {
+            for (key, val) in self.bucket(bucket, &mut visited)? {
+                pairs.push((self.item_data(key)?, self.item_data(val)?));
+            }
+        }
+        let entries: HashMap<&[u8], &[u8]> = (pairs.iter())
+            .filter_map(|(key, val)| Some((&**key, val.strip_prefix(&[ENTRY])?)))
+            .collect();
+        let mut res = Vec::new();
+        for (key, val) in &pairs {
+            let Ok(name) = String::from_utf8(key.clone()) else {
+                continue;
+            };
+            let entry = match val.split_first() {
+                Some((&ENTRY, entry)) => entry,
+                Some((&ALIAS, target)) => {
+                    let target = target.split(|&c| c == b'\0').next().unwrap();
+                    match entries.get(target) {
+                        Some(entry) => entry,
+                        None => continue,
+                    }
+                }
+                _ => continue,
+            };
+            res.push((name, entry.to_vec()));
+        }
+        Ok(res)
+    }
+
+    /// Returns the keys and values of all pages of `bucket`. Every page belongs to a
+    /// single bucket, pages in `visited` were already read for another bucket (or
+    /// form a cycle) in corrupted databases.
+    fn bucket(
+        &mut self,
+        bucket: u32,
+        visited: &mut HashSet<u32>,
+    ) -> Result<Vec<(Item, Item)>, Error> {
+        let spare = bucket
+            .checked_add(1)
+            .and_then(u32::checked_next_power_of_two)
+            .ok_or(Error::InvalidHashedDb)?
+            .trailing_zeros();
+        let spare = *self
+            .spares
+            .get(spare as usize)
+            .ok_or(Error::InvalidHashedDb)?;
+        let mut page_num = bucket.checked_add(spare).ok_or(Error::InvalidHashedDb)?;
+        let mut res = Vec::new();
+        loop {
+            if !visited.insert(page_num) {
+                return Err(Error::InvalidHashedDb);
+            }
+            let page = self.page(page_num)?;
+            if !matches!(page[25], P_HASH | P_HASH_UNSORTED) {
+                return Err(Error::InvalidHashedDb);
+            }
+            let entries = usize::from(self.u16_at(&page, 20)?);
+            let mut end = self.page_size;
+            let mut items = Vec::with_capacity(entries);
+            for i in 0..entries {
+                let start = usize::from(self.u16_at(&page, PAGE_HEADER + 2 * i)?);
+                if start < PAGE_HEADER + 2 * entries || start >= end {
+                    return Err(Error::InvalidHashedDb);
+                }
+                items.push(self.item(&page[start..end])?);
+                end = start;
+            }
+            let mut items = items.into_iter();
+            while let (Some(key), Some(val)) = (items.next(), items.next()) {
+                res.push((key, val));
+            }
+            page_num = self.u32_at(&page, 16)?;
+            if page_num == 0 {
+                return Ok(res);
+            }
+        }
+    }
+
+    fn item(&self, item: &[u8]) -> Result<Item, Error> {
+        match item[0] {
+            H_KEYDATA => Ok(Item::Inline(item[1..].to_vec())),
+            H_OFFPAGE => Ok(Item::Overflow {
+                page: self.u32_at(item, 4)?,
+                len: self.u32_at(item, 8)?,
+            }),
+            // duplicates are never stored by ncurses
+            _ => Err(Error::InvalidHashedDb),
+        }
+    }
+
+    fn item_eq(&mut self, item: Item, key: &[u8]) -> Result<bool, Error> {
+        match item {
+            Item::Inline(data) => Ok(data == key),
+            Item::Overflow { len, .. } if len as usize != key.len() => Ok(false),
+            item => Ok(self.item_data(item)? == key),
+        }
+    }
+
+    fn item_data(&mut self, item: Item) -> Result<Vec<u8>, Error> {
+        let (mut page_num, len) = match item {
+            Item::Inline(data) => return Ok(data),
+            Item::Overflow { page, len } => (page, len),
+        };
+        // one byte more for the prefix that marks entries
+        if u64::from(len) > MAX_ENTRY_SIZE + 1 {
+            return Err(Error::EntryTooLarge {
+                size: u64::from(len) - 1,
+            });
+        }
+        let mut res = Vec::with_capacity(len as usize);
+        while page_num != 0 && res.len() < len as usize {
+            let page = self.page(page_num)?;
+            let page_len = usize::from(self.u16_at(&page, 22)?);
+            // empty pages would never finish the item
+            if page[25] != P_OVERFLOW || page_len == 0 || PAGE_HEADER + page_len > self.page_size {
+                return Err(Error::InvalidHashedDb);
+            }
+            res.extend_from_slice(&page[PAGE_HEADER..PAGE_HEADER + page_len]);
+            page_num = self.u32_at(&page, 16)?;
+        }
+        if res.len() != len as usize {
+            return Err(Error::InvalidHashedDb);
+        }
+        Ok(res)
+    }
+
+    fn page(&mut self, page_num: u32) -> Result<Vec<u8>, Error> {
+        if page_num > self.last_page {
+            return Err(Error::InvalidHashedDb);
+        }
+        let mut page = vec![0; self.page_size];
+        let off = u64::from(page_num) * self.page_size as u64;
+        self.reader.seek(SeekFrom::Start(off))?;
+        self.reader.read_exact(&mut page)?;
+        Ok(page)
+    }
+
+    fn u16_at(&self, data: &[u8], off: usize) -> Result<u16, Error> {
+        let bytes = data.get(off..off + 2).ok_or(Error::InvalidHashedDb)?;
+        let bytes = bytes.try_into().unwrap();
+        if self.big_endian {
+            Ok(u16::from_be_bytes(bytes))
+        } else {
+            Ok(u16::from_le_bytes(bytes))
+        }
+    }
+
+    fn u32_at(&self, data: &[u8], off: usize) -> Result<u32, Error> {
+        let bytes = data.get(off..off + 4).ok_or(Error::InvalidHashedDb)?;
+        let bytes = bytes.try_into().unwrap();
+        if self.big_endian {
+            Ok(u32::from_be_bytes(bytes))
+        } else {
+            Ok(u32::from_le_bytes(bytes))
+        }
+    }
+}
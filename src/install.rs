@@ -0,0 +1,112 @@
+//! Installation of compiled entries into terminfo directory trees
+
+use std::fs::{self, File};
+use std::io::{self, Write};
+use std::path::{Path, PathBuf};
+
+use crate::{is_valid_name, Error, TermInfo};
+
+/// Layout of a terminfo directory tree.
+/// [`TermInfo::from_name`] searches both layouts.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum Layout {
+    /// Entries are stored in a directory named after their first letter (`x/xterm`)
+    #[default]
+    FirstLetter,
+    /// Entries are stored in a directory named after the hex code of their first letter
+    /// (`78/xterm`), used by ncurses on case-insensitive filesystems
+    Hex,
+}
+
+impl Layout {
+    /// Returns the path of the entry `name` within the terminfo directory `dir`
+    pub fn entry_path(self, dir: impl AsRef<Path>, name: &str) -> Option<PathBuf> {
+        let first = name.chars().next()?;
+        let subdir = match self {
+            Layout::FirstLetter => first.to_string(),
+            Layout::Hex => format!("{:x}", first as usize),
+        };
+        Some(dir.as_ref().join(subdir).join(name))
+    }
+}
+
+impl TermInfo {
+    /// Compiles the entry into the terminfo directory `dir` using the [`Layout::FirstLetter`]
+    /// layout. See [`TermInfo::install_with_layout`].
+    ///
+    /// # Example
+    /// ```no_run
+    /// use termini::TermInfo;
+    ///
+    /// let info = TermInfo::from_source("myterm|my terminal, cols#80, lines#24,").unwrap();
+    /// let home = std::env::var("HOME").unwrap();
+    /// info.install(format!("{home}/.terminfo")).unwrap();
+    /// ```
+    pub fn install(&self, dir: impl AsRef<Path>) -> Result<PathBuf, Error> {
+        self.install_with_layout(dir, Layout::default())
+    }
+
+    /// Compiles the entry into the terminfo directory `dir` so it can be found by
+    /// [`TermInfo::from_name`]. The entry is written for its name and hard linked
+    /// (or copied if linking fails) for all of its aliases. Existing entries are replaced.
+    /// Returns the path of the compiled entry.
+    ///
+    /// Returns [`Error::InvalidTermName`] if the name or an alias is not a valid terminal
+    /// name (see [`is_valid_name`]), nothing is written in that case.
+    pub fn install_with_layout(
+        &self,
+        dir: impl AsRef<Path>,
+        layout: Layout,
+    ) -> Result<PathBuf, Error> {
+        let dir = dir.as_ref();
+        let path = entry_path(dir, &self.name, layout)?;
+        let aliases = self
+            .aliases
+            .iter()
+            .map(|alias| entry_path(dir, alias, layout))
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mut compiled = Vec::new();
+        self.write_to(&mut compiled)?;
+        create_parent(&path)?;
+        remove_existing(&path)?;
+        File::create(&path)?.write_all(&compiled)?;
+
+        for alias in aliases {
+            if alias == path {
+                continue;
+            }
+            create_parent(&alias)?;
+            remove_existing(&alias)?;
+            if fs::hard_link(&path, &alias).is_err() {
+                fs::copy(&path, &alias)?;
+            }
+        }
+        Ok(path)
+    }
+}
+
+fn entry_path(dir: &Path, name: &str, layout: Layout) -> Result<PathBuf, Error> {
+    // the same names that can be looked up
+    if !is_valid_name(name) {
+        return Err(Error::InvalidTermName(name.to_owned()));
+    }
+    layout
+        .entry_path(dir, name)
+        .ok_or_else(|| Error::InvalidTermName(name.to_owned()))
+}
+
+fn create_parent(path: &Path) -> io::Result<()> {
+    match path.parent() {
+        Some(parent) => fs::create_dir_all(parent),
+        None => Ok(()),
+    }
+}
+
+/// Removes the file at `path` so that existing hard links are not modified
+fn remove_existing(path: &Path) -> io::Result<()> {
+    match fs::remove_file(path) {
+        Err(err) if err.kind() != io::ErrorKind::NotFound => Err(err),
+        _ => Ok(()),
+    }
+}
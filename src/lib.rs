@@ -1,15 +1,38 @@
 #![forbid(unsafe_code)]
 
-use std::collections::HashMap;
+use std::collections::BTreeMap;
 use std::fs::{self, File};
+use std::io::{self, Read};
 use std::path::{Path, PathBuf};
 use std::string::FromUtf8Error;
-use std::{env, io};
 
+pub use crate::borrowed::TermInfoRef;
 pub use crate::capabilities::{BoolCapability, NumberCapability, StringCapability};
-
+use crate::capabilities::{BOOLS, NUMBERS, STRINGS};
+pub use crate::diff::{Capability, Difference, State};
+pub use crate::dump::{Dump, DumpOptions, SortOrder};
+pub use crate::fallback::{Fallbacks, Resolved};
+pub use crate::install::Layout;
+use crate::parsing::MAX_ENTRY_SIZE;
+pub use crate::search::{is_valid_name, Entry, NotFound, SearchPath};
+pub use crate::source::SourceDatabase;
+use crate::source::{CANCELLED_NUMBER, CANCELLED_STRING};
+pub use crate::tparm::{expand, expand_to, expand_to_fmt, Param, Program, Variables};
+pub use crate::writing::Format;
+
+mod borrowed;
 mod capabilities;
+mod diff;
+mod dump;
+mod fallback;
+mod hashed;
+mod install;
+mod padding;
 mod parsing;
+mod search;
+mod source;
+mod tparm;
+mod writing;
 
 #[cfg(test)]
 mod tests;
@@ -22,6 +45,7 @@ pub struct TermInfo {
     pub aliases: Vec<String>,
     data: TermInfoData,
     extended: Extended,
+    path: Option<PathBuf>,
 }
 
 #[derive(Debug, PartialEq, Eq, Clone, Copy)]
@@ -32,17 +56,58 @@ pub enum Value<'a> {
     Number(i32),
 }
 
+/// Errors returned by this crate. New variants may be added in minor releases.
 #[derive(Debug)]
+#[non_exhaustive]
 pub enum Error {
-    NotFound,
+    /// no entry with the requested name exists, see [`NotFound`] for the paths that
+    /// were searched
+    NotFound(Box<NotFound>),
     InvalidMagicNum(i16),
     Io(io::Error),
     NoNames,
     NamesMissingNull,
     StringMissingNull,
-    OutOfBoundString { off: u16, table_size: u16 },
+    OutOfBoundString {
+        off: u16,
+        table_size: u16,
+    },
     InvalidUtf8(FromUtf8Error),
     InvalidNames,
+    /// the capability to expand is not present (or not a string)
+    MissingCapability,
+    /// a parameterized string is malformed at `offset`
+    InvalidTemplate {
+        offset: usize,
+    },
+    /// a parameterized string popped more values than it pushed
+    StackUnderflow,
+    /// a parameterized string pushed more values than the stack holds
+    StackOverflow,
+    /// a parameter is a number where a string is expected or the other way around
+    TypeMismatch,
+    /// writing an expanded string to a [`std::fmt::Write`] failed
+    Fmt(std::fmt::Error),
+    /// the terminfo source is malformed in `line`
+    InvalidSource {
+        line: usize,
+    },
+    /// a `use=` capability references an entry that doesn't exist
+    MissingUse(String),
+    /// the `use=` references of the entry form a cycle
+    UseCycle(String),
+    /// the entry exceeds the limits of the compiled format
+    TableTooLarge,
+    /// no capability of the type has the index
+    InvalidCapabilityIndex(usize),
+    /// the terminal name can't be looked up, see [`is_valid_name`]
+    InvalidTermName(String),
+    /// the file is larger than the maximum size of a compiled entry
+    EntryTooLarge {
+        size: u64,
+    },
+    /// a hashed terminfo database is corrupted or uses an unsupported format
+    InvalidHashedDb,
 }
 
 impl std::error::Error for Error {
@@ -51,6 +116,7 @@ impl std::error::Error for Error {
         match self {
             Error::Io(source) => Some(source as _),
             Error::InvalidUtf8(source) => Some(source as _),
+            Error::Fmt(source) => Some(source as _),
             _ => None,
         }
     }
@@ -58,7 +124,7 @@ impl std::error::Error for Error {
 impl std::fmt::Display for Error {
     fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
         match self {
-            Error::NotFound {} => write!(f, "Terminfo file not found"),
+            Error::NotFound(not_found) => write!(f, "{not_found}"),
             Error::InvalidMagicNum(num) => write!(f, "bad magic number {num} in terminfo header",),
             Error::Io(_) => write!(f, "reading terminfo failed",),
             Error::NoNames => write!(f, "no names exposed, need at least one"),
@@ -71,16 +137,48 @@ impl std::fmt::Display for Error {
                 "string offset {off} outside data table (size: {table_size})",
             ),
             Error::InvalidUtf8(_) => write!(f, "terminfo string is invalid ASCII/UTF-8",),
-            Error::InvalidNames {} => write!(f, "no names exposed, need at least one"),
+            Error::InvalidNames => write!(f, "no names exposed, need at least one"),
+            Error::MissingCapability => write!(f, "capability not present in terminfo"),
+            Error::InvalidTemplate { offset } => {
+                write!(f, "malformed parameterized string at offset {offset}")
+            }
+            Error::StackUnderflow => write!(f, "parameterized string popped from empty stack"),
+            Error::StackOverflow => write!(f, "parameterized string exceeded stack size"),
+            Error::TypeMismatch => write!(f, "parameter has the wrong type for operation"),
+            Error::Fmt(_) => write!(f, "writing expanded string failed"),
+            Error::InvalidSource { line } => write!(f, "invalid terminfo source in line {line}"),
+            Error::MissingUse(name) => write!(f, "use={name} references unknown entry"),
+            Error::UseCycle(name) => write!(f, "use= references of {name} form a cycle"),
+            Error::TableTooLarge => write!(f, "string table exceeds maximum size"),
+            Error::InvalidCapabilityIndex(index) => write!(f, "no capability with index {index}"),
+            Error::InvalidTermName(name) => write!(f, "invalid terminal name {name:?}"),
+            Error::InvalidHashedDb => write!(f, "invalid or unsupported hashed terminfo database"),
+            Error::EntryTooLarge { size } => {
+                write!(
+                    f,
+                    "terminfo file of {size} bytes exceeds maximum entry size"
+                )
+            }
         }
     }
 }
+impl std::convert::From<NotFound> for Error {
+    fn from(not_found: NotFound) -> Self {
+        Error::NotFound(Box::new(not_found))
+    }
+}
 impl std::convert::From<io::Error> for Error {
     #[allow(deprecated)]
     fn from(source: io::Error) -> Self {
         Error::Io(source)
     }
 }
+impl std::convert::From<std::fmt::Error> for Error {
+    #[allow(deprecated)]
+    fn from(source: std::fmt::Error) -> Self {
+        Error::Fmt(source)
+    }
+}
 impl std::convert::From<FromUtf8Error> for Error {
     #[allow(deprecated)]
     fn from(source: FromUtf8Error) -> Self {
@@ -90,7 +188,8 @@ impl std::convert::From<FromUtf8Error> for Error {
 
 #[derive(Debug, Default)]
 struct Extended {
-    capabilities: HashMap<String, ValueStorage>,
+    /// sorted by name like in compiled entries
+    capabilities: BTreeMap<String, ValueStorage>,
     table: Box<[u8]>,
 }
 
@@ -99,11 +198,15 @@ enum ValueStorage {
     True,
     String(u16),
     Number(i32),
+    /// `cap@`, stored as a cancelled string in compiled entries
+    Cancelled,
 }
 
 #[derive(Debug, Default)]
 struct TermInfoData {
     bools: Box<[bool]>,
+    /// absent numbers are always stored as -1 and cancelled numbers as -2,
+    /// regardless of the format the entry was read from
     numbers: Box<[i32]>,
     strings: Box<[u16]>,
     str_table: Box<[u8]>,
@@ -127,6 +230,14 @@ fn get_str_with_offset(table: &[u8], start: u16, offset: u16) -> Option<&[u8]> {
     Some(res)
 }
 
+/// Returns a string value as [`Value::Utf8String`] if it's valid UTF-8
+fn string_value(raw: &[u8]) -> Value<'_> {
+    match std::str::from_utf8(raw) {
+        Ok(res) => Value::Utf8String(res),
+        Err(_) => Value::RawString(raw),
+    }
+}
+
 impl TermInfoData {
     /// gets a string from the `str_table` starting at `start`.
     ///
@@ -191,11 +302,10 @@ impl TermInfo {
     /// }
     /// ```
     pub fn number_cap(&self, cap: NumberCapability) -> Option<i32> {
-        self.data
-            .numbers
-            .get(cap as usize)
-            .copied()
-            .filter(|&val| val != 0xffff)
+        match self.number_cap_state(cap) {
+            State::Present(Value::Number(val)) => Some(val),
+            _ => None,
+        }
     }
 
     /// Returns the bool value for the capability, if the capability is not present,
@@ -217,94 +327,377 @@ impl TermInfo {
         self.data.bools.get(cap as usize).copied().unwrap_or(false)
     }
 
-    pub fn extended_cap(&self, name: &str) -> Option<Value> {
-        let res = match *self.extended.capabilities.get(name)? {
+    pub fn extended_cap(&self, name: &str) -> Option<Value<'_>> {
+        self.extended_value(*self.extended.capabilities.get(name)?)
+    }
+
+    fn extended_value(&self, val: ValueStorage) -> Option<Value<'_>> {
+        let res = match val {
             ValueStorage::True => Value::True,
             ValueStorage::String(off) => {
-                let raw = get_str_with_offset(&self.extended.table, off, 0)?;
-                match std::str::from_utf8(raw) {
-                    Ok(res) => Value::Utf8String(res),
-                    Err(_) => Value::RawString(raw),
-                }
+                string_value(get_str_with_offset(&self.extended.table, off, 0)?)
             }
             ValueStorage::Number(val) => Value::Number(val),
+            ValueStorage::Cancelled => return None,
         };
         Some(res)
     }
 
-    /// Create TermInfo database, using TERM environment var.
-    pub fn from_env() -> Result<Self, Error> {
-        if let Ok(term) = std::env::var("TERM") {
-            TermInfo::from_name(term.as_str())
-        } else {
-            Err(Error::NotFound)
+    /// Returns the value of the standard or extended capability `name`.
+    /// `name` can be a terminfo name (`cup`) or a termcap name (`cm`), terminfo names
+    /// take precedence over extended capabilities, which take precedence over termcap names.
+    ///
+    /// # Example
+    /// ```
+    /// use termini::TermInfo;
+    ///
+    /// if let Ok(info) = TermInfo::from_env() {
+    ///     println!("{:?}", info.get("colors"));
+    ///     println!("{:?}", info.get("Smulx"));
+    /// }
+    /// ```
+    pub fn get(&self, name: &str) -> Option<Value<'_>> {
+        if let Some(cap) = BoolCapability::from_name(name) {
+            return self.bool_value(cap);
+        }
+        if let Some(cap) = NumberCapability::from_name(name) {
+            return self.number_cap(cap).map(Value::Number);
+        }
+        if let Some(cap) = StringCapability::from_name(name) {
+            return self.raw_string_cap(cap).map(string_value);
         }
+        if let Some(val) = self.extended_cap(name) {
+            return Some(val);
+        }
+        if let Some(cap) = BoolCapability::from_termcap_name(name) {
+            return self.bool_value(cap);
+        }
+        if let Some(cap) = NumberCapability::from_termcap_name(name) {
+            return self.number_cap(cap).map(Value::Number);
+        }
+        let cap = StringCapability::from_termcap_name(name)?;
+        self.raw_string_cap(cap).map(string_value)
     }
 
-    /// Create TermInfo database for the given name
-    pub fn from_name(name: &str) -> Result<Self, Error> {
-        let first = name.chars().next().ok_or(Error::NotFound)?;
+    fn bool_value(&self, cap: BoolCapability) -> Option<Value<'_>> {
+        self.flag_cap(cap).then_some(Value::True)
+    }
 
-        // See https://manpages.debian.org/buster/ncurses-bin/TermInfo.5.en.html#Fetching_Compiled_Descriptions
-        let mut search = Vec::<PathBuf>::new();
+    fn bool_state(&self, cap: BoolCapability) -> State<'_> {
+        self.bool_value(cap).map_or(State::Absent, State::Present)
+    }
 
-        if let Some(dir) = env::var_os("TERMINFO") {
-            search.push(dir.into());
-        } else if let Some(mut home) = home::home_dir() {
-            home.push(".terminfo");
-            search.push(home);
+    /// Returns whether the number capability is present, absent or cancelled (`cap@`).
+    /// Cancelled capabilities are only retained by compiled entries if they
+    /// override a value inherited with `use=`.
+    ///
+    /// # Example
+    /// ```
+    /// use termini::{NumberCapability, State, TermInfo, Value};
+    ///
+    /// let info = TermInfo::from_source("myterm|my terminal, colors@, cols#80,").unwrap();
+    /// assert_eq!(info.number_cap_state(NumberCapability::MaxColors), State::Cancelled);
+    /// assert_eq!(info.number_cap_state(NumberCapability::Lines), State::Absent);
+    /// assert_eq!(info.number_cap_state(NumberCapability::Columns), State::Present(Value::Number(80)));
+    /// ```
+    pub fn number_cap_state(&self, cap: NumberCapability) -> State<'_> {
+        match self.data.numbers.get(cap as usize) {
+            Some(&CANCELLED_NUMBER) => State::Cancelled,
+            Some(&num) if num >= 0 => State::Present(Value::Number(num)),
+            _ => State::Absent,
         }
+    }
 
-        if let Ok(dirs) = env::var("TERMINFO_DIRS") {
-            for dir in dirs.split(':') {
-                search.push(dir.into());
-            }
+    /// Returns whether the string capability is present, absent or cancelled (`cap@`),
+    /// see [`TermInfo::number_cap_state`]
+    pub fn string_cap_state(&self, cap: StringCapability) -> State<'_> {
+        match self.data.strings.get(cap as usize) {
+            Some(&CANCELLED_STRING) => State::Cancelled,
+            Some(&off) => self
+                .data
+                .get_str_at(off)
+                .map_or(State::Absent, |val| State::Present(string_value(val))),
+            None => State::Absent,
         }
+    }
 
-        // handle non-FHS systems like Termux
-        if let Ok(prefix) = env::var("PREFIX") {
-            let path = Path::new(&prefix);
-            search.push(path.join("etc/terminfo"));
-            search.push(path.join("lib/terminfo"));
-            search.push(path.join("share/terminfo"));
+    /// Returns whether the extended capability is present, absent or cancelled (`cap@`),
+    /// see [`TermInfo::number_cap_state`]
+    pub fn extended_cap_state(&self, name: &str) -> State<'_> {
+        match self.extended.capabilities.get(name) {
+            Some(ValueStorage::Cancelled) => State::Cancelled,
+            Some(&val) => self
+                .extended_value(val)
+                .map_or(State::Absent, State::Present),
+            None => State::Absent,
         }
+    }
 
-        search.push("/etc/terminfo".into());
-        search.push("/lib/terminfo".into());
-        search.push("/usr/share/terminfo".into());
-        search.push("/boot/system/data/terminfo".into());
+    /// Returns an iterator over all bool capabilities that are set
+    ///
+    /// # Example
+    /// ```
+    /// use termini::TermInfo;
+    ///
+    /// if let Ok(info) = TermInfo::from_env() {
+    ///     for (cap, _) in info.bool_caps() {
+    ///         println!("{}", cap.name());
+    ///     }
+    /// }
+    /// ```
+    pub fn bool_caps(&self) -> impl Iterator<Item = (BoolCapability, Value<'_>)> + '_ {
+        (self.data.bools.iter().zip(BOOLS))
+            .filter(|(&val, _)| val)
+            .map(|(_, cap)| (cap, Value::True))
+    }
 
-        for path in search {
-            if fs::metadata(&path).is_err() {
-                continue;
-            }
+    /// Returns an iterator over all number capabilities that are present
+    pub fn number_caps(&self) -> impl Iterator<Item = (NumberCapability, Value<'_>)> + '_ {
+        (self.data.numbers.iter().zip(NUMBERS))
+            .filter(|(&val, _)| val >= 0)
+            .map(|(&val, cap)| (cap, Value::Number(val)))
+    }
 
-            // Check standard location.
-            {
-                let mut path = path.clone();
-                path.push(first.to_string());
-                path.push(name);
+    /// Returns an iterator over all string capabilities that are present
+    pub fn string_caps(&self) -> impl Iterator<Item = (StringCapability, Value<'_>)> + '_ {
+        (self.data.strings.iter().zip(STRINGS))
+            .filter_map(|(&off, cap)| Some((cap, string_value(self.data.get_str_at(off)?))))
+    }
 
-                if fs::metadata(&path).is_ok() {
-                    return Self::from_path(&path);
-                }
-            }
+    /// Returns an iterator over all extended capabilities that are present, sorted by name
+    ///
+    /// # Example
+    /// ```
+    /// use termini::TermInfo;
+    ///
+    /// if let Ok(info) = TermInfo::from_env() {
+    ///     for (name, val) in info.extended_caps() {
+    ///         println!("{name}: {val:?}");
+    ///     }
+    /// }
+    /// ```
+    pub fn extended_caps(&self) -> impl Iterator<Item = (&str, Value<'_>)> + '_ {
+        let caps = self.extended.capabilities.iter();
+        caps.filter_map(|(name, &val)| Some((name.as_str(), self.extended_value(val)?)))
+    }
 
-            // Check non-standard location.
-            let mut path = path.clone();
-            path.push(format!("{:x}", first as usize));
-            path.push(name);
+    /// Expands the parameterized string capability `cap` with `params`.
+    /// Returns [`Error::MissingCapability`] if the capability is not present.
+    ///
+    /// # Arguments
+    /// * `cap` - string capability
+    /// * `params` - parameters referenced by the capability (`%p1`..`%p9`)
+    /// * `vars` - static variables, retained between expansions
+    ///
+    /// # Example
+    /// ```
+    /// use termini::{StringCapability, TermInfo, Variables};
+    ///
+    /// if let Ok(info) = TermInfo::from_env() {
+    ///     let mut vars = Variables::new();
+    ///     let res = info.expand(StringCapability::CursorAddress, &[4.into(), 2.into()], &mut vars);
+    ///     println!("{:?}", res);
+    /// }
+    /// ```
+    pub fn expand(
+        &self,
+        cap: StringCapability,
+        params: &[Param],
+        vars: &mut Variables,
+    ) -> Result<Vec<u8>, Error> {
+        let template = self.raw_string_cap(cap).ok_or(Error::MissingCapability)?;
+        expand(template, params, vars)
+    }
 
-            if fs::metadata(&path).is_ok() {
-                return Self::from_path(&path);
-            }
+    /// Compiles the parameterized string capability `cap` for repeated expansion.
+    /// Returns [`Error::MissingCapability`] if the capability is not present.
+    ///
+    /// # Example
+    /// ```
+    /// use termini::{StringCapability, TermInfo, Variables};
+    ///
+    /// if let Ok(info) = TermInfo::from_env() {
+    ///     if let Ok(cup) = info.compile(StringCapability::CursorAddress) {
+    ///         let mut vars = Variables::new();
+    ///         println!("{:?}", cup.expand(&[4.into(), 2.into()], &mut vars));
+    ///     }
+    /// }
+    /// ```
+    pub fn compile(&self, cap: StringCapability) -> Result<Program, Error> {
+        let template = self.raw_string_cap(cap).ok_or(Error::MissingCapability)?;
+        Program::compile(template)
+    }
+
+    /// Expands the parameterized string capability `cap` with `params` directly into `out`.
+    /// Returns [`Error::MissingCapability`] if the capability is not present.
+    ///
+    /// # Arguments
+    /// * `cap` - string capability
+    /// * `params` - parameters referenced by the capability (`%p1`..`%p9`)
+    /// * `vars` - static variables, retained between expansions
+    /// * `out` - destination of the expanded string, should be buffered
+    ///
+    /// # Example
+    /// ```
+    /// use std::io::{stdout, BufWriter};
+    /// use termini::{StringCapability, TermInfo, Variables};
+    ///
+    /// if let Ok(info) = TermInfo::from_env() {
+    ///     let mut vars = Variables::new();
+    ///     let mut out = BufWriter::new(stdout().lock());
+    ///     let _ = info.expand_to(StringCapability::SetAnsiForeground, &[1.into()], &mut vars, &mut out);
+    /// }
+    /// ```
+    pub fn expand_to(
+        &self,
+        cap: StringCapability,
+        params: &[Param],
+        vars: &mut Variables,
+        out: impl io::Write,
+    ) -> Result<(), Error> {
+        let template = self.raw_string_cap(cap).ok_or(Error::MissingCapability)?;
+        expand_to(template, params, vars, out)
+    }
+
+    /// Expands the parameterized string capability `cap` with `params` directly into the
+    /// [`fmt::Write`](std::fmt::Write) `out`, see [`TermInfo::expand_to`].
+    /// Returns [`Error::MissingCapability`] if the capability is not present and
+    /// [`Error::Fmt`] if writing fails or the expansion is not valid UTF-8.
+    ///
+    /// # Example
+    /// ```
+    /// use termini::{StringCapability, TermInfo, Variables};
+    ///
+    /// if let Ok(info) = TermInfo::from_env() {
+    ///     let mut vars = Variables::new();
+    ///     let mut out = String::new();
+    ///     let _ = info.expand_to_fmt(StringCapability::CursorAddress, &[4.into(), 2.into()], &mut vars, &mut out);
+    /// }
+    /// ```
+    pub fn expand_to_fmt(
+        &self,
+        cap: StringCapability,
+        params: &[Param],
+        vars: &mut Variables,
+        out: impl std::fmt::Write,
+    ) -> Result<(), Error> {
+        let template = self.raw_string_cap(cap).ok_or(Error::MissingCapability)?;
+        expand_to_fmt(template, params, vars, out)
+    }
+
+    /// Expands the extended string capability `name` with `params` directly into `out`.
+    /// Returns [`Error::MissingCapability`] if the capability is not present or not a string.
+    ///
+    /// # Example
+    /// ```
+    /// use termini::{TermInfo, Variables};
+    ///
+    /// if let Ok(info) = TermInfo::from_env() {
+    ///     let mut vars = Variables::new();
+    ///     let mut out = Vec::new();
+    ///     // colored underline
+    ///     let _ = info.expand_extended_to("Setulc", &[0xff0000.into()], &mut vars, &mut out);
+    /// }
+    /// ```
+    pub fn expand_extended_to(
+        &self,
+        name: &str,
+        params: &[Param],
+        vars: &mut Variables,
+        out: impl io::Write,
+    ) -> Result<(), Error> {
+        expand_to(self.extended_template(name)?, params, vars, out)
+    }
+
+    /// Expands the extended string capability `name` with `params` directly into the
+    /// [`fmt::Write`](std::fmt::Write) `out`, see [`TermInfo::expand_extended_to`].
+    /// Returns [`Error::MissingCapability`] if the capability is not present or not a string
+    /// and [`Error::Fmt`] if writing fails or the expansion is not valid UTF-8.
+    pub fn expand_extended_to_fmt(
+        &self,
+        name: &str,
+        params: &[Param],
+        vars: &mut Variables,
+        out: impl std::fmt::Write,
+    ) -> Result<(), Error> {
+        expand_to_fmt(self.extended_template(name)?, params, vars, out)
+    }
+
+    fn extended_template(&self, name: &str) -> Result<&[u8], Error> {
+        match self.extended_cap(name) {
+            Some(Value::RawString(template)) => Ok(template),
+            Some(Value::Utf8String(template)) => Ok(template.as_bytes()),
+            _ => Err(Error::MissingCapability),
         }
+    }
 
-        Err(Error::NotFound)
+    /// Create TermInfo database, using TERM environment var.
+    /// Returns [`Error::NotFound`] if TERM is not set or empty.
+    pub fn from_env() -> Result<Self, Error> {
+        TermInfo::from_term(std::env::var("TERM").ok())
     }
 
-    /// Rad a TermInfo database from a given path
+    fn from_term(term: Option<String>) -> Result<Self, Error> {
+        match term {
+            Some(term) if !term.is_empty() => TermInfo::from_name(&term),
+            _ => Err(NotFound::new("").into()),
+        }
+    }
+
+    /// Create TermInfo database for the given name, searching the directories
+    /// of [`SearchPath::from_env`]
+    pub fn from_name(name: &str) -> Result<Self, Error> {
+        TermInfo::from_name_in(&SearchPath::from_env(), name)
+    }
+
+    /// Rad a TermInfo database from a given path.
+    /// The file is read into memory with a single read and then parsed from memory.
+    /// Returns [`Error::EntryTooLarge`] for files larger than 32KiB (the maximum size
+    /// of compiled entries) and an IO error if `file` is not a regular file.
     pub fn from_path(file: impl AsRef<Path>) -> Result<TermInfo, Error> {
-        TermInfo::parse(File::open(file)?)
+        let path = file.as_ref();
+        let data = read_entry(path)?;
+        let mut res = TermInfo::from(TermInfoRef::parse(&data)?);
+        res.path = Some(path.to_owned());
+        Ok(res)
+    }
+
+    /// Returns the path of the file the entry was loaded from, if it was loaded
+    /// with [`TermInfo::from_path`] (or one of the functions that search for entries)
+    ///
+    /// # Example
+    /// ```
+    /// use termini::TermInfo;
+    ///
+    /// if let Ok(info) = TermInfo::from_env() {
+    ///     println!("loaded {} from {:?}", info.name, info.path());
+    /// }
+    /// ```
+    pub fn path(&self) -> Option<&Path> {
+        self.path.as_deref()
+    }
+}
+
+/// Reads a compiled entry. Only regular files (or symlinks to regular files) are read
+/// and larger files than [`MAX_ENTRY_SIZE`] are rejected without reading them.
+fn read_entry(path: &Path) -> Result<Vec<u8>, Error> {
+    // checked before opening the file because opening a FIFO blocks
+    if !fs::metadata(path)?.is_file() {
+        return Err(io::Error::new(io::ErrorKind::InvalidInput, "not a regular file").into());
+    }
+    let file = File::open(path)?;
+    let len = file.metadata()?.len();
+    if len > MAX_ENTRY_SIZE {
+        return Err(Error::EntryTooLarge { size: len });
+    }
+    // one byte more than the file size so the end of the file is detected by the first read
+    let mut data = Vec::with_capacity(len as usize + 1);
+    // the file may have grown since its size was checked
+    file.take(MAX_ENTRY_SIZE + 1).read_to_end(&mut data)?;
+    if data.len() as u64 > MAX_ENTRY_SIZE {
+        return Err(Error::EntryTooLarge {
+            size: data.len() as u64,
+        });
     }
+    Ok(data)
 }
@@ -0,0 +1,151 @@
+//! Handling of padding specifications (`$<5>`, `$<2*/>`) embedded in string capabilities.
+
+use std::io;
+use std::time::Duration;
+
+use crate::tparm::{IoOutput, Output};
+use crate::{BoolCapability, Error, NumberCapability, StringCapability, TermInfo};
+
+/// bits sent per character, used by ncurses to convert delays into pad characters
+const BAUD_BYTE: u64 = 9;
+
+/// A parsed `$<n[.n][*][/]>` padding specification
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+struct PaddingSpec {
+    /// delay in tenths of a millisecond
+    tenths: u64,
+    /// `*`, the delay is multiplied by the number of affected lines
+    proportional: bool,
+    /// `/`, the delay is mandatory even if xon/xoff is used
+    mandatory: bool,
+}
+
+/// Parses a padding specification at the start of `src` and returns it with its length.
+/// Returns `None` if `src` doesn't start with a valid padding specification.
+fn parse_padding(src: &[u8]) -> Option<(PaddingSpec, usize)> {
+    let mut pos = 2;
+    if !src.starts_with(b"$<") {
+        return None;
+    }
+    let mut spec = PaddingSpec {
+        tenths: 0,
+        proportional: false,
+        mandatory: false,
+    };
+    let digits = pos;
+    while let Some(c @ b'0'..=b'9') = src.get(pos) {
+        spec.tenths = spec
+            .tenths
+            .saturating_mul(10)
+            .saturating_add((c - b'0').into());
+        pos += 1;
+    }
+    spec.tenths = spec.tenths.saturating_mul(10);
+    if src.get(pos) == Some(&b'.') {
+        pos += 1;
+        // only the first decimal is significant
+        if let Some(c @ b'0'..=b'9') = src.get(pos) {
+            spec.tenths += u64::from(c - b'0');
+        }
+        while let Some(b'0'..=b'9') = src.get(pos) {
+            pos += 1;
+        }
+    }
+    if pos == digits {
+        return None;
+    }
+    loop {
+        match src.get(pos)? {
+            b'*' => spec.proportional = true,
+            b'/' => spec.mandatory = true,
+            b'>' => return Some((spec, pos + 1)),
+            _ => return None,
+        }
+        pos += 1;
+    }
+}
+
+impl TermInfo {
+    /// Writes the (expanded) string capability `s` to `out` while honoring the padding
+    /// specifications (`$<n>`) it contains, like `tputs` from ncurses.
+    ///
+    /// Padding is only applied if it's mandatory (`$<n/>`) or if the terminal doesn't use
+    /// xon/xoff flow control and `baud_rate` is at least the `pb` (padding baud rate)
+    /// capability. Otherwise the padding specification is stripped.
+    /// Delays are emitted as pad characters (`pad`, NUL by default). If the terminal has
+    /// no pad character (`npc`) or `baud_rate` is zero, `out` is flushed and the delay is
+    /// reported to `delay` instead.
+    ///
+    /// # Arguments
+    /// * `s` - string to output, usually the result of [`TermInfo::expand`]
+    /// * `affected_lines` - number of lines affected, used for proportional padding (`$<n*>`)
+    /// * `baud_rate` - output speed of the terminal
+    /// * `out` - destination of the output
+    /// * `delay` - called with delays that can not be expressed with pad characters
+    ///
+    /// # Example
+    /// ```
+    /// use termini::{StringCapability, TermInfo};
+    ///
+    /// if let Ok(info) = TermInfo::from_env() {
+    ///     if let Some(clear) = info.raw_string_cap(StringCapability::ClearScreen) {
+    ///         let mut out = std::io::stdout();
+    ///         let _ = info.tputs(clear, 1, 9600, &mut out, std::thread::sleep);
+    ///     }
+    /// }
+    /// ```
+    pub fn tputs(
+        &self,
+        s: &[u8],
+        affected_lines: u32,
+        baud_rate: u32,
+        mut out: impl io::Write,
+        mut delay: impl FnMut(Duration),
+    ) -> Result<(), Error> {
+        let padding_baud_rate = self
+            .number_cap(NumberCapability::PaddingBaudRate)
+            .unwrap_or(0);
+        let normal_delay = !self.flag_cap(BoolCapability::XonXoff)
+            && i64::from(baud_rate) >= padding_baud_rate.into();
+        let pad_char = if self.flag_cap(BoolCapability::NoPadChar) || baud_rate == 0 {
+            None
+        } else {
+            let pad = self.raw_string_cap(StringCapability::PadChar);
+            Some(pad.and_then(|pad| pad.first().copied()).unwrap_or(0))
+        };
+
+        let mut rest = s;
+        while let Some(start) = rest.windows(2).position(|window| window == b"$<") {
+            out.write_all(&rest[..start])?;
+            rest = &rest[start..];
+            let Some((spec, len)) = parse_padding(rest) else {
+                out.write_all(&rest[..2])?;
+                rest = &rest[2..];
+                continue;
+            };
+            rest = &rest[len..];
+            if !spec.mandatory && !normal_delay {
+                continue;
+            }
+            let mut tenths = spec.tenths;
+            if spec.proportional {
+                tenths = tenths.saturating_mul(affected_lines.into());
+            }
+            if tenths == 0 {
+                continue;
+            }
+            match pad_char {
+                Some(pad) => {
+                    let count = tenths.saturating_mul(baud_rate.into()) / (BAUD_BYTE * 10_000);
+                    IoOutput(&mut out).write_repeated(pad, count as usize)?;
+                }
+                None => {
+                    out.flush()?;
+                    delay(Duration::from_micros(tenths.saturating_mul(100)));
+                }
+            }
+        }
+        out.write_all(rest)?;
+        Ok(())
+    }
+}
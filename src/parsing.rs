@@ -1,51 +1,51 @@
-use crate::{get_str_with_offset, Error, Extended, TermInfo, TermInfoData, ValueStorage};
-use std::collections::HashMap;
-use std::io;
-use std::io::Read;
+use crate::borrowed::ExtendedRef;
+use crate::source::{ABSENT_NUMBER, CANCELLED_NUMBER};
+use crate::{get_str_with_offset, Error, TermInfo, TermInfoRef};
+use std::io::{self, Read};
 
 /// magic number octal 0432 for legacy ncurses terminfo
-const MAGIC_LEGACY: i16 = 0x11A;
+pub(crate) const MAGIC_LEGACY: i16 = 0x11A;
 /// magic number octal 01036 for new ncurses terminfo
-const MAGIC_32BIT: i16 = 0x21E;
+pub(crate) const MAGIC_32BIT: i16 = 0x21E;
+/// maximum size of a compiled entry in bytes (`MAX_ENTRY_SIZE` in ncurses)
+pub(crate) const MAX_ENTRY_SIZE: u64 = 32768;
+
+/// The bools, numbers, string offsets and string table of a compiled entry
+/// (or of its extended capabilities) borrowed from the compiled data
+#[derive(Debug, Clone, Copy, Default)]
+pub(crate) struct Sections<'a> {
+    number_32bit: bool,
+    bools: &'a [u8],
+    numbers: &'a [u8],
+    strings: &'a [u8],
+    pub(crate) str_table: &'a [u8],
+}
 
-impl TermInfoData {
-    /// Create terminfo database by parse byte-array directly
-    pub fn parse<R: io::Read>(
-        mut reader: R,
-        numbers_32bit: bool,
+impl<'a> Sections<'a> {
+    fn parse(
+        reader: &mut Reader<'a>,
+        number_32bit: bool,
         bool_cnt: u16,
         numbers_cnt: u16,
         string_cnt: u16,
         table_bytes: u16,
         aligned: bool,
-    ) -> Result<TermInfoData, Error> {
-        let bools = (0..bool_cnt)
-            .map(|_| match read_byte(&mut reader) {
-                Err(e) => Err(e),
-                Ok(1) => Ok(true),
-                Ok(_) => Ok(false),
-            })
-            .collect::<Result<_, _>>()?;
-
+    ) -> Result<Sections<'a>, Error> {
+        let bools = reader.take(bool_cnt.into())?;
         if bool_cnt % 2 == aligned.into() {
-            read_byte(&mut reader)?; // compensate for padding
+            reader.take(1)?; // compensate for padding
         }
-
-        let numbers = (0..numbers_cnt)
-            .map(|_| {
-                if numbers_32bit {
-                    read_i32(&mut reader)
-                } else {
-                    read_i16(&mut reader).map(i32::from)
-                }
-            })
-            .collect::<Result<_, _>>()?;
-
-        let strings: Box<[_]> = (0..string_cnt)
-            .map(|_| read_u16(&mut reader))
-            .collect::<Result<_, _>>()?;
-
-        for &off in &*strings {
+        let number_size = if number_32bit { 4 } else { 2 };
+        let numbers = reader.take(usize::from(numbers_cnt) * number_size)?;
+        let strings = reader.take(usize::from(string_cnt) * 2)?;
+        let res = Sections {
+            number_32bit,
+            bools,
+            numbers,
+            strings,
+            str_table: reader.take(table_bytes.into())?,
+        };
+        for off in res.string_offsets() {
             if matches!(off, 0..=0xfffd if off > table_bytes) {
                 return Err(Error::OutOfBoundString {
                     off,
@@ -53,28 +53,67 @@ impl TermInfoData {
                 });
             }
         }
+        Ok(res)
+    }
 
-        let mut str_table = Vec::new();
-        let read = reader
-            .take(table_bytes.into())
-            .read_to_end(&mut str_table)?;
-        if read != table_bytes as usize {
-            return Err(io::Error::new(io::ErrorKind::Other, "end of file").into());
-        }
+    pub(crate) fn bools(&self) -> impl Iterator<Item = bool> + 'a {
+        self.bools.iter().map(|&val| val == 1)
+    }
 
-        Ok(TermInfoData {
-            bools,
-            numbers,
-            strings,
-            str_table: str_table.into_boxed_slice(),
+    pub(crate) fn bool(&self, i: usize) -> bool {
+        self.bools.get(i) == Some(&1)
+    }
+
+    /// Returns all numbers. Only -1 and -2 are used by ncurses for absent and cancelled
+    /// numbers, any other negative number is treated as absent
+    pub(crate) fn numbers(&self) -> impl Iterator<Item = i32> + 'a {
+        let number_32bit = self.number_32bit;
+        let number_size = if number_32bit { 4 } else { 2 };
+        self.numbers.chunks_exact(number_size).map(move |val| {
+            let val = if number_32bit {
+                i32::from_le_bytes([val[0], val[1], val[2], val[3]])
+            } else {
+                i16::from_le_bytes([val[0], val[1]]).into()
+            };
+            match val {
+                CANCELLED_NUMBER => CANCELLED_NUMBER,
+                _ if val < 0 => ABSENT_NUMBER,
+                _ => val,
+            }
         })
     }
+
+    pub(crate) fn number(&self, i: usize) -> i32 {
+        self.numbers().nth(i).unwrap_or(ABSENT_NUMBER)
+    }
+
+    pub(crate) fn string_offsets(&self) -> impl Iterator<Item = u16> + 'a {
+        (self.strings.chunks_exact(2)).map(|off| u16::from_le_bytes([off[0], off[1]]))
+    }
+
+    pub(crate) fn string_offset(&self, i: usize) -> Option<u16> {
+        self.string_offsets().nth(i)
+    }
 }
-impl TermInfo {
-    /// Create terminfo database by parse byte-array directly
-    pub fn parse<R: io::Read>(mut reader: R) -> Result<TermInfo, Error> {
+
+impl<'a> TermInfoRef<'a> {
+    /// Parses a compiled terminfo entry without copying any of its data.
+    /// Returns the same errors as [`TermInfo::parse`].
+    ///
+    /// # Example
+    /// ```
+    /// use termini::{NumberCapability, TermInfoRef};
+    ///
+    /// static XTERM: &[u8] = include_bytes!("../tests/xterm");
+    ///
+    /// let info = TermInfoRef::parse(XTERM).unwrap();
+    /// assert_eq!(info.name, "xterm");
+    /// assert_eq!(info.number_cap(NumberCapability::MaxColors), Some(8));
+    /// ```
+    pub fn parse(data: &'a [u8]) -> Result<TermInfoRef<'a>, Error> {
+        let mut reader = Reader { data };
         // read the magic number.
-        let magic = read_i16(&mut reader)?;
+        let magic = reader.i16()?;
 
         let number_32bit = match magic {
             MAGIC_LEGACY => false,
@@ -82,26 +121,23 @@ impl TermInfo {
             num => return Err(Error::InvalidMagicNum(num)),
         };
 
-        let names_bytes = read_non_neg_i16(&mut reader)?;
-        let bool_count = read_non_neg_i16(&mut reader)?;
-        let numbers_count = read_non_neg_i16(&mut reader)?;
-        let string_count = read_non_neg_i16(&mut reader)?;
-        let string_table_bytes = read_non_neg_i16(&mut reader)?;
+        let names_bytes = reader.non_neg_i16()?;
+        let bool_count = reader.non_neg_i16()?;
+        let numbers_count = reader.non_neg_i16()?;
+        let string_count = reader.non_neg_i16()?;
+        let string_table_bytes = reader.non_neg_i16()?;
 
         if names_bytes == 0 {
             return Err(Error::NoNames);
         }
 
-        let term_names = read_str(&mut reader, names_bytes - 1)?;
-        let mut term_names = term_names.split('|').map(|it| it.trim().to_owned());
-        let name = term_names.next().unwrap();
-        let mut aliases: Vec<_> = term_names.collect();
+        let names = utf8(reader.take(usize::from(names_bytes) - 1)?)?;
 
-        if read_byte(&mut reader)? != b'\0' {
+        if reader.take(1)? != b"\0" {
             return Err(Error::NamesMissingNull);
         }
 
-        let data = TermInfoData::parse(
+        let data = Sections::parse(
             &mut reader,
             number_32bit,
             bool_count,
@@ -115,25 +151,45 @@ impl TermInfo {
             try_parse_ext_capabilities(reader, number_32bit, string_table_bytes % 2 == 1)
                 .unwrap_or_default();
 
-        let res = TermInfo {
+        let mut parts = names.split('|').map(str::trim);
+        let name = parts.next().unwrap();
+        let description = parts.next_back().unwrap_or_default();
+
+        Ok(TermInfoRef {
             name,
-            description: aliases.pop().unwrap_or_default(),
-            aliases,
+            description,
+            names,
             data,
             extended,
-        };
+        })
+    }
+}
 
-        Ok(res)
+impl TermInfo {
+    /// Create terminfo database by parse byte-array directly.
+    /// The reader is read to the end, use [`TermInfoRef::parse`] to parse
+    /// a byte slice without copying it.
+    /// Returns [`Error::EntryTooLarge`] without reading further if the reader
+    /// contains more than 32KiB (the maximum size of compiled entries).
+    pub fn parse<R: Read>(reader: R) -> Result<TermInfo, Error> {
+        let mut data = Vec::new();
+        reader.take(MAX_ENTRY_SIZE + 1).read_to_end(&mut data)?;
+        if data.len() as u64 > MAX_ENTRY_SIZE {
+            return Err(Error::EntryTooLarge {
+                size: data.len() as u64,
+            });
+        }
+        TermInfoRef::parse(&data).map(TermInfo::from)
     }
 }
 
 fn try_parse_ext_capabilities(
-    mut reader: impl io::Read,
+    mut reader: Reader<'_>,
     number_32bit: bool,
     unaligned: bool,
-) -> Result<Extended, Error> {
+) -> Result<ExtendedRef<'_>, Error> {
     if unaligned {
-        read_byte(&mut reader)?; // compensate for padding
+        reader.take(1)?; // compensate for padding
     }
 
     // the term(5) manpage doesn't describe this properly
@@ -141,127 +197,98 @@ fn try_parse_ext_capabilities(
     // actually the number of strings in the table (including names),
     // and what it calls the "last offset in the string table" is
     // actually the size in bytes of the string table
-    let bool_count = read_non_neg_i16(&mut reader)?;
-    let num_count = read_non_neg_i16(&mut reader)?;
-    let string_count = read_non_neg_i16(&mut reader)?;
-    let num_strings_in_table = read_non_neg_i16(&mut reader)?;
-    let table_bytes = read_non_neg_i16(&mut reader)?;
-    // debug_assert!(num_strings_in_table >= bool_count + num_count + 2 * string_count);
-
-    // TODO bounds checks
-    let data = TermInfoData::parse(
+    let bool_count = reader.non_neg_i16()?;
+    let num_count = reader.non_neg_i16()?;
+    let string_count = reader.non_neg_i16()?;
+    let _num_strings_in_table = reader.non_neg_i16()?;
+    let table_bytes = reader.non_neg_i16()?;
+
+    // absent and cancelled string values are not stored in the table so the offsets
+    // are counted directly: one per string value followed by one per name
+    let offset_count = (bool_count + num_count)
+        .checked_add(2 * string_count)
+        .ok_or(Error::InvalidNames)?;
+
+    let mut data = Sections::parse(
         &mut reader,
         number_32bit,
         bool_count,
         num_count,
-        num_strings_in_table,
+        offset_count,
         table_bytes,
         true,
     )?;
 
-    if string_count as usize >= data.strings.len() {
+    if string_count >= offset_count {
         return Err(Error::InvalidNames);
     }
 
-    let names_off = data.strings[..string_count as usize]
-        .iter()
-        .rev()
-        .filter_map(|&off| Some(off + data.get_str_at(off)?.len() as u16))
+    // the names start after the last string value (if there are any)
+    let table = data.str_table;
+    let names_off = (data.string_offsets().take(string_count.into()))
+        .filter_map(|off| Some(off as usize + get_str_with_offset(table, off, 0)?.len() + 1))
         .max()
         .unwrap_or(0)
-        + 1;
-
-    let mut names = data.strings[string_count as usize..].iter().map(|&off| {
-        if matches!(off, 0..=0xfffd if off as usize + names_off as usize >= table_bytes as usize) {
-            return Some(Err(Error::OutOfBoundString {
-                off: off + names_off,
+        .min(table.len());
+    data.str_table = &table[..names_off];
+    let names = &table[names_off..];
+
+    for off in data.string_offsets().skip(string_count.into()) {
+        if matches!(off, 0..=0xfffd if off as usize >= names.len()) {
+            return Err(Error::OutOfBoundString {
+                off: off.saturating_add(names_off as u16),
                 table_size: table_bytes,
-            }));
-        }
-        let res = get_str_with_offset(&*data.str_table, off, names_off)?.to_owned();
-        match String::from_utf8(res) {
-            Ok(res) => Some(Ok(res)),
-            Err(err) => Some(Err(err.into())),
-        }
-    });
-
-    let mut capabilities = HashMap::with_capacity((bool_count + num_count + string_count) as usize);
-
-    for (&val, name) in data.bools.iter().zip(&mut names) {
-        if let Some(name) = name {
-            if val {
-                capabilities.insert(name?, ValueStorage::True);
-            }
-        }
-    }
-
-    for (&val, name) in data.numbers.iter().zip(&mut names) {
-        if let Some(name) = name {
-            if val != 0xffff {
-                capabilities.insert(name?, ValueStorage::Number(val));
-            }
+            });
         }
-    }
-    for (&val, name) in data.strings.iter().zip(&mut names) {
-        if let Some(name) = name {
-            if !matches!(val, 0xffff | 0xfffe) {
-                capabilities.insert(name?, ValueStorage::String(val));
-            }
+        if let Some(name) = get_str_with_offset(names, off, 0) {
+            utf8(name)?;
         }
     }
 
-    let mut str_table = Vec::from(data.str_table);
-    str_table.truncate(names_off as usize);
-
-    Ok(Extended {
-        capabilities,
-        table: str_table.into_boxed_slice(),
+    Ok(ExtendedRef {
+        data,
+        string_count: string_count.into(),
+        names,
     })
 }
 
-fn read_i16<R: io::Read>(mut data: R) -> Result<i16, Error> {
-    let mut buf = [0; 2];
-    data.read_exact(&mut buf)?;
-    Ok(i16::from_le_bytes(buf))
+fn utf8(bytes: &[u8]) -> Result<&str, Error> {
+    match std::str::from_utf8(bytes) {
+        Ok(res) => Ok(res),
+        // only allocate to create the error
+        Err(_) => Err(String::from_utf8(bytes.to_vec()).unwrap_err().into()),
+    }
 }
 
-fn read_u16<R: io::Read>(mut data: R) -> Result<u16, Error> {
-    let mut buf = [0; 2];
-    data.read_exact(&mut buf)?;
-    Ok(u16::from_le_bytes(buf))
+/// Reads the header fields and sections of a compiled entry from a byte slice
+struct Reader<'a> {
+    data: &'a [u8],
 }
 
-/// According to the spec, these fields must be >= -1 where -1 means that the
-/// feature is not
-/// supported. Using 0 instead of -1 works because we skip sections with length
-/// 0.
-fn read_non_neg_i16(data: impl io::Read) -> Result<u16, Error> {
-    match read_i16(data)? {
-        n @ 0.. => Ok(n as u16),
-        -1 => Ok(0),
-        _ => Err(Error::InvalidNames),
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], Error> {
+        if len > self.data.len() {
+            return Err(io::Error::new(io::ErrorKind::UnexpectedEof, "end of file").into());
+        }
+        let (res, rest) = self.data.split_at(len);
+        self.data = rest;
+        Ok(res)
     }
-}
 
-fn read_i32<R: io::Read>(mut data: R) -> Result<i32, Error> {
-    let mut buf = [0; 4];
-    data.read_exact(&mut buf)?;
-    Ok(i32::from_le_bytes(buf))
-}
-
-fn read_str(data: impl io::Read, size: u16) -> Result<String, Error> {
-    let mut bytes = Vec::new();
-    let read = data.take(size.into()).read_to_end(&mut bytes)?;
-    if read != size as usize {
-        return Err(io::Error::new(io::ErrorKind::Other, "end of file").into());
+    fn i16(&mut self) -> Result<i16, Error> {
+        let bytes = self.take(2)?;
+        Ok(i16::from_le_bytes([bytes[0], bytes[1]]))
     }
-    let bytes = String::from_utf8(bytes)?;
-    Ok(bytes)
-}
 
-fn read_byte(r: &mut impl io::Read) -> io::Result<u8> {
-    match r.bytes().next() {
-        Some(s) => s,
-        None => Err(io::Error::new(io::ErrorKind::Other, "end of file")),
+    /// According to the spec, these fields must be >= -1 where -1 means that the
+    /// feature is not
+    /// supported. Using 0 instead of -1 works because we skip sections with length
+    /// 0.
+    fn non_neg_i16(&mut self) -> Result<u16, Error> {
+        match self.i16()? {
+            n @ 0.. => Ok(n as u16),
+            -1 => Ok(0),
+            _ => Err(Error::InvalidNames),
+        }
     }
 }
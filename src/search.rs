@@ -0,0 +1,483 @@
+//! The directories searched for compiled entries, see [`SearchPath`]
+
+use std::cmp::Reverse;
+use std::collections::HashMap;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::{env, fmt};
+
+use crate::hashed::HashedDb;
+use crate::{Error, Layout, TermInfo, TermInfoRef};
+
+/// directories searched after the directories from the environment
+const SYSTEM_DIRS: [&str; 4] = [
+    "/etc/terminfo",
+    "/lib/terminfo",
+    "/usr/share/terminfo",
+    // Haiku
+    "/boot/system/data/terminfo",
+];
+
+/// maximum length of a terminal name in bytes
+const MAX_NAME_LEN: usize = 128;
+
+/// Returns whether `name` is a terminal name that can be looked up in terminfo
+/// directories: 1 to 128 ASCII letters, digits, `+`, `-`, `.` and `_` starting with a
+/// letter or digit. Names that contain path separators or other special characters are
+/// rejected so they can't refer to files outside of a terminfo directory.
+///
+/// # Example
+/// ```
+/// use termini::is_valid_name;
+///
+/// assert!(is_valid_name("xterm-256color"));
+/// assert!(is_valid_name("screen.xterm-new"));
+/// assert!(!is_valid_name("../../../tmp/evil"));
+/// assert!(!is_valid_name(""));
+/// ```
+pub fn is_valid_name(name: &str) -> bool {
+    let valid_char = |c: u8| c.is_ascii_alphanumeric() || matches!(c, b'+' | b'-' | b'.' | b'_');
+    match name.as_bytes() {
+        [first, rest @ ..] => {
+            name.len() <= MAX_NAME_LEN
+                && first.is_ascii_alphanumeric()
+                && rest.iter().all(|&c| valid_char(c))
+        }
+        [] => false,
+    }
+}
+
+/// The locations that were searched for an entry that could not be found,
+/// returned by [`Error::NotFound`]
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct NotFound {
+    /// the name of the entry
+    pub name: String,
+    /// all directories (and hashed databases) of the search path
+    pub dirs: Vec<PathBuf>,
+    /// the paths of the compiled entries and hashed databases that were checked for the
+    /// entry, directories that don't exist are skipped
+    pub candidates: Vec<PathBuf>,
+    /// hashed databases that are corrupted or couldn't be read, they are skipped
+    /// like ncurses does
+    pub skipped: Vec<PathBuf>,
+}
+
+impl NotFound {
+    pub(crate) fn new(name: &str) -> NotFound {
+        NotFound {
+            name: name.to_owned(),
+            ..NotFound::default()
+        }
+    }
+
+    /// Returns the names of up to five entries in [`NotFound::dirs`] that are similar
+    /// to the name, the most similar name comes first.
+    ///
+    /// This is expensive: every directory is listed and every hashed database is read
+    /// each time it's called, which is why the suggestions are neither computed when
+    /// an entry isn't found nor included when the error is displayed.
+    ///
+    /// # Example
+    /// ```
+    /// use termini::{Error, TermInfo};
+    ///
+    /// if let Err(Error::NotFound(not_found)) = TermInfo::from_name("xterm-256colour") {
+    ///     println!("did you mean {:?}", not_found.suggestions());
+    /// }
+    /// ```
+    pub fn suggestions(&self) -> Vec<String> {
+        if !is_valid_name(&self.name) {
+            return Vec::new();
+        }
+        let search: SearchPath = self.dirs.iter().collect();
+        suggestions(&self.name, search.names())
+    }
+}
+
+impl fmt::Display for NotFound {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        if self.name.is_empty() {
+            return write!(f, "Terminfo file not found");
+        }
+        write!(f, "Terminfo entry {} not found", self.name)?;
+        for (i, path) in self.candidates.iter().enumerate() {
+            let sep = if i == 0 { ", searched" } else { "," };
+            write!(f, "{sep} {}", path.display())?;
+        }
+        for (i, path) in self.skipped.iter().enumerate() {
+            let sep = if i == 0 {
+                "; skipped invalid database"
+            } else {
+                ","
+            };
+            write!(f, "{sep} {}", path.display())?;
+        }
+        Ok(())
+    }
+}
+
+/// An ordered list of terminfo directories, used by [`TermInfo::from_name_in`].
+/// Directories that don't exist are skipped when searching. Files with a `.db`
+/// extension are searched as hashed databases (`terminfo.db`) like ncurses
+/// builds configured with `--with-hashed-db` use. Like ncurses, databases that
+/// are corrupted or can't be read are skipped.
+///
+/// # Example
+/// ```
+/// use termini::{SearchPath, TermInfo};
+///
+/// let mut search = SearchPath::from_env();
+/// search.prepend("/opt/myapp/terminfo");
+/// if let Ok(info) = TermInfo::from_name_in(&search, "xterm") {
+///     println!("{}", info.description);
+/// }
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct SearchPath {
+    dirs: Vec<PathBuf>,
+}
+
+impl SearchPath {
+    /// Creates an empty search path
+    pub fn new() -> SearchPath {
+        SearchPath::default()
+    }
+
+    /// Creates the search path used by ncurses (and [`TermInfo::from_name`]) from the
+    /// environment of the process:
+    ///
+    /// 1. `$TERMINFO`, or `~/.terminfo` if `$TERMINFO` is not set
+    /// 2. the directories in `$TERMINFO_DIRS`
+    /// 3. `$PREFIX/{etc,lib,share}/terminfo` for non-FHS systems like Termux
+    /// 4. `/etc/terminfo`, `/lib/terminfo`, `/usr/share/terminfo` and
+    ///    `/boot/system/data/terminfo`
+    pub fn from_env() -> SearchPath {
+        // See https://manpages.debian.org/buster/ncurses-bin/TermInfo.5.en.html#Fetching_Compiled_Descriptions
+        let mut search = SearchPath::new();
+
+        if let Some(dir) = env::var_os("TERMINFO") {
+            search.append(dir);
+        } else if let Some(home) = home::home_dir() {
+            search.append(home.join(".terminfo"));
+        }
+
+        if let Ok(dirs) = env::var("TERMINFO_DIRS") {
+            for dir in dirs.split(':') {
+                search.append(dir);
+            }
+        }
+
+        // handle non-FHS systems like Termux
+        if let Ok(prefix) = env::var("PREFIX") {
+            let path = Path::new(&prefix);
+            search.append(path.join("etc/terminfo"));
+            search.append(path.join("lib/terminfo"));
+            search.append(path.join("share/terminfo"));
+        }
+
+        for dir in SYSTEM_DIRS {
+            search.append(dir);
+        }
+        search
+    }
+
+    /// Returns the directories in the order they are searched
+    pub fn dirs(&self) -> &[PathBuf] {
+        &self.dirs
+    }
+
+    /// Adds `dir` to the front of the search path so it's searched first
+    pub fn prepend(&mut self, dir: impl Into<PathBuf>) {
+        self.dirs.insert(0, dir.into());
+    }
+
+    /// Adds `dir` to the end of the search path so it's searched last
+    pub fn append(&mut self, dir: impl Into<PathBuf>) {
+        self.dirs.push(dir.into());
+    }
+
+    /// Removes all occurrences of `dir` from the search path,
+    /// returns whether `dir` was part of the search path
+    pub fn remove(&mut self, dir: impl AsRef<Path>) -> bool {
+        let len = self.dirs.len();
+        self.dirs.retain(|path| path != dir.as_ref());
+        len != self.dirs.len()
+    }
+
+    /// Returns the path of the first compiled entry for `name` (or of the hashed database
+    /// that contains it). Returns [`Error::NotFound`] with the directories and paths that
+    /// were searched if there is no entry for `name`.
+    /// Nothing is searched if `name` is not a valid terminal name (see [`is_valid_name`]).
+    pub fn find(&self, name: &str) -> Result<PathBuf, Error> {
+        match self.search(name)? {
+            Found::File(path) | Found::Hashed(path, _) => Ok(path),
+        }
+    }
+
+    /// Returns all terminal types available on the search path sorted by name.
+    /// Like for [`TermInfo::from_name_in`], an entry in a directory shadows entries
+    /// with the same name in later directories. Every entry is listed once with the file
+    /// (or hashed database) that [`TermInfo::from_name_in`] loads for its name.
+    /// Files that are not valid compiled entries are skipped.
+    ///
+    /// # Example
+    /// ```
+    /// use termini::SearchPath;
+    ///
+    /// for entry in SearchPath::from_env().entries() {
+    ///     println!("{}: {} ({})", entry.name, entry.description, entry.path.display());
+    /// }
+    /// ```
+    pub fn entries(&self) -> Vec<Entry> {
+        // the entry each name resolves to and the index of its directory
+        let mut resolved: HashMap<String, (usize, Entry)> = HashMap::new();
+        for (i, dir) in self.dirs.iter().enumerate() {
+            let Ok(meta) = fs::metadata(dir) else {
+                continue;
+            };
+            let entries = if meta.is_file() && dir.extension().is_some_and(|ext| ext == "db") {
+                db_entries(dir)
+            } else if meta.is_dir() {
+                dir_entries(dir)
+            } else {
+                continue;
+            };
+            for (name, entry) in entries {
+                resolved.entry(name).or_insert((i, entry));
+            }
+        }
+
+        let mut res: Vec<_> = (resolved.iter())
+            .filter(|(name, (_, entry))| {
+                // entries that can only be found by one of their aliases are listed as well
+                **name == entry.name || !resolved.contains_key(&entry.name)
+            })
+            .map(|(_, entry)| entry)
+            .collect();
+        res.sort_by_key(|&(i, entry)| (&entry.name, *i));
+        res.dedup_by_key(|(_, entry)| &entry.name);
+        res.into_iter().map(|(_, entry)| entry.clone()).collect()
+    }
+
+    fn search(&self, name: &str) -> Result<Found, Error> {
+        let mut not_found = NotFound::new(name);
+        if !is_valid_name(name) {
+            return Err(not_found.into());
+        }
+        for dir in &self.dirs {
+            not_found.dirs.push(dir.clone());
+            let Ok(meta) = fs::metadata(dir) else {
+                continue;
+            };
+            if meta.is_file() && dir.extension().is_some_and(|ext| ext == "db") {
+                not_found.candidates.push(dir.clone());
+                match HashedDb::open(dir).and_then(|mut db| db.entry(name)) {
+                    Ok(Some(entry)) => return Ok(Found::Hashed(dir.clone(), entry)),
+                    Ok(None) => (),
+                    Err(_) => not_found.skipped.push(dir.clone()),
+                }
+                continue;
+            }
+            if !meta.is_dir() {
+                continue;
+            }
+
+            // Check standard location first, then the non-standard location.
+            for layout in [Layout::FirstLetter, Layout::Hex] {
+                let Some(path) = layout.entry_path(dir, name) else {
+                    return Err(not_found.into());
+                };
+                // symlinks are followed but only to regular files
+                if fs::metadata(&path).is_ok_and(|meta| meta.is_file()) {
+                    return Ok(Found::File(path));
+                }
+                not_found.candidates.push(path);
+            }
+        }
+        Err(not_found.into())
+    }
+
+    /// Returns the names of all entries and aliases on the search path
+    fn names(&self) -> Vec<String> {
+        let mut res = Vec::new();
+        for dir in &self.dirs {
+            let Ok(meta) = fs::metadata(dir) else {
+                continue;
+            };
+            if meta.is_file() && dir.extension().is_some_and(|ext| ext == "db") {
+                let names = HashedDb::open(dir).and_then(|mut db| db.names());
+                res.extend(names.into_iter().flatten());
+            } else if meta.is_dir() {
+                res.extend(dir_names(dir).into_iter().map(|(name, _)| name));
+            }
+        }
+        res
+    }
+}
+
+/// A terminal type available on a [`SearchPath`], see [`SearchPath::entries`]
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Entry {
+    pub name: String,
+    pub description: String,
+    pub aliases: Vec<String>,
+    /// the compiled entry or hashed database the entry is loaded from
+    pub path: PathBuf,
+}
+
+impl Entry {
+    fn parse(data: &[u8], path: &Path) -> Option<Entry> {
+        let info = TermInfoRef::parse(data).ok()?;
+        Some(Entry {
+            name: info.name.to_owned(),
+            description: info.description.to_owned(),
+            aliases: info.aliases().map(str::to_owned).collect(),
+            path: path.to_owned(),
+        })
+    }
+}
+
+/// Returns the names of all compiled entries in the terminfo directory `dir` with
+/// the entry they refer to, names in the [`Layout::FirstLetter`] layout come first
+fn dir_entries(dir: &Path) -> Vec<(String, Entry)> {
+    (dir_names(dir).into_iter())
+        .filter_map(|(name, path)| {
+            let data = crate::read_entry(&path).ok()?;
+            Some((name, Entry::parse(&data, &path)?))
+        })
+        .collect()
+}
+
+/// Returns the names of all files in the terminfo directory `dir` that can be found by
+/// [`SearchPath::find`] with their path, sorted by name
+fn dir_names(dir: &Path) -> Vec<(String, PathBuf)> {
+    let mut files = Vec::new();
+    let Ok(subdirs) = fs::read_dir(dir) else {
+        return Vec::new();
+    };
+    for subdir in subdirs.flatten() {
+        let Ok(names) = fs::read_dir(subdir.path()) else {
+            continue;
+        };
+        for file in names.flatten() {
+            let Ok(name) = file.file_name().into_string() else {
+                continue;
+            };
+            if !is_valid_name(&name) {
+                continue;
+            }
+            let layout = [Layout::FirstLetter, Layout::Hex]
+                .into_iter()
+                .position(|layout| layout.entry_path(dir, &name) == Some(file.path()));
+            if let Some(layout) = layout {
+                files.push((name, layout, file.path()));
+            }
+        }
+    }
+    // names in the first letter layout are found first
+    files.sort();
+    files.dedup_by(|(name, ..), (prev, ..)| name == prev);
+    (files.into_iter())
+        .map(|(name, _, path)| (name, path))
+        .collect()
+}
+
+/// maximum number of names returned by [`NotFound::suggestions`]
+const MAX_SUGGESTIONS: usize = 5;
+
+/// Returns the names in `names` that are similar to `name`, ranked by their edit
+/// distance and the length of their common prefix with `name`
+fn suggestions(name: &str, names: impl IntoIterator<Item = String>) -> Vec<String> {
+    let name = name.to_ascii_lowercase();
+    let max_distance = (name.len() / 3).max(2);
+    let mut res: Vec<_> = (names.into_iter())
+        .filter_map(|candidate| {
+            let lowercase = candidate.to_ascii_lowercase();
+            let distance = edit_distance(name.as_bytes(), lowercase.as_bytes());
+            let prefix = (name.bytes().zip(lowercase.bytes()))
+                .take_while(|(a, b)| a == b)
+                .count();
+            (distance <= max_distance).then_some((distance, Reverse(prefix), candidate))
+        })
+        .collect();
+    res.sort();
+    res.dedup_by(|(.., a), (.., b)| a == b);
+    (res.into_iter())
+        .take(MAX_SUGGESTIONS)
+        .map(|(.., candidate)| candidate)
+        .collect()
+}
+
+/// The number of insertions, deletions, substitutions and transpositions of adjacent
+/// characters required to turn `a` into `b` (optimal string alignment distance)
+fn edit_distance(a: &[u8], b: &[u8]) -> usize {
+    // distances of the prefixes of `a` to the prefixes of `b` for the current and
+    // the two previous characters of `a`
+    let mut prev2 = vec![0; b.len() + 1];
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut cur = vec![0; b.len() + 1];
+    for i in 1..=a.len() {
+        cur[0] = i;
+        for j in 1..=b.len() {
+            let cost = usize::from(a[i - 1] != b[j - 1]);
+            cur[j] = (prev[j] + 1).min(cur[j - 1] + 1).min(prev[j - 1] + cost);
+            if i > 1 && j > 1 && a[i - 1] == b[j - 2] && a[i - 2] == b[j - 1] {
+                cur[j] = cur[j].min(prev2[j - 2] + 1);
+            }
+        }
+        std::mem::swap(&mut prev2, &mut prev);
+        std::mem::swap(&mut prev, &mut cur);
+    }
+    prev[b.len()]
+}
+
+/// Returns the names of all entries and aliases in the hashed database `db` with the
+/// entry they refer to
+fn db_entries(db: &Path) -> Vec<(String, Entry)> {
+    let entries = HashedDb::open(db).and_then(|mut db| db.entries());
+    (entries.into_iter().flatten())
+        .filter(|(name, _)| is_valid_name(name))
+        .filter_map(|(name, data)| Some((name, Entry::parse(&data, db)?)))
+        .collect()
+}
+
+/// A compiled entry found on the search path
+enum Found {
+    File(PathBuf),
+    /// the path of the hashed database and the compiled entry
+    Hashed(PathBuf, Vec<u8>),
+}
+
+impl<P: Into<PathBuf>> FromIterator<P> for SearchPath {
+    fn from_iter<I: IntoIterator<Item = P>>(dirs: I) -> SearchPath {
+        SearchPath {
+            dirs: dirs.into_iter().map(Into::into).collect(),
+        }
+    }
+}
+
+impl TermInfo {
+    /// Create TermInfo database for the given name by searching the directories
+    /// of `search` in order.
+    /// Returns [`Error::InvalidTermName`] if `name` is not a valid terminal name
+    /// (see [`is_valid_name`]) so that names from untrusted sources like `TERM` of a
+    /// remote client can't be used to read files outside of the terminfo directories.
+    pub fn from_name_in(search: &SearchPath, name: &str) -> Result<TermInfo, Error> {
+        if !is_valid_name(name) {
+            return Err(Error::InvalidTermName(name.to_owned()));
+        }
+        TermInfo::from_found(search.search(name)?)
+    }
+
+    fn from_found(found: Found) -> Result<TermInfo, Error> {
+        match found {
+            Found::File(path) => TermInfo::from_path(path),
+            Found::Hashed(path, entry) => {
+                let mut res = TermInfo::from(TermInfoRef::parse(&entry)?);
+                res.path = Some(path);
+                Ok(res)
+            }
+        }
+    }
+}
@@ -0,0 +1,461 @@
+//! Parser for the terminfo source format (as read by `tic` and written by `infocmp`),
+//! see [`terminfo(5)`](https://manpages.debian.org/bookworm/ncurses-bin/terminfo.5.en.html).
+
+use std::collections::{BTreeMap, HashMap};
+use std::fs;
+use std::path::Path;
+
+use crate::capabilities::{BOOL_NAMES, NUMBER_NAMES, STRING_NAMES};
+use crate::{Error, Extended, NotFound, TermInfo, TermInfoData, ValueStorage};
+
+/// string offset marking an absent string capability
+const ABSENT_STRING: u16 = 0xffff;
+/// string offset marking a cancelled string capability
+pub(crate) const CANCELLED_STRING: u16 = 0xfffe;
+/// marks an absent number capability
+pub(crate) const ABSENT_NUMBER: i32 = -1;
+/// marks a cancelled number capability
+pub(crate) const CANCELLED_NUMBER: i32 = -2;
+
+/// The value of a capability in a source entry
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub(crate) enum SourceValue {
+    True,
+    Number(i32),
+    String(Vec<u8>),
+    /// `cap@`
+    Cancelled,
+}
+
+/// A single entry of a terminfo source file
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub(crate) struct SourceEntry {
+    /// the primary name, aliases and (if there is more than one name) the description
+    pub(crate) names: Vec<String>,
+    /// capabilities in the order they appear in the entry
+    pub(crate) capabilities: Vec<(String, SourceValue)>,
+    /// the entries referenced by `use=` in the order they appear in the entry
+    pub(crate) uses: Vec<String>,
+    /// the line the entry starts on (starting at 1)
+    pub(crate) line: usize,
+}
+
+/// Parses all entries in terminfo source `src`.
+pub(crate) fn parse_entries(src: &str) -> Result<Vec<SourceEntry>, Error> {
+    let mut entries = Vec::new();
+    // (first line, text of the entry with continuation lines joined)
+    let mut current: Option<(usize, String)> = None;
+    for (i, line) in src.lines().enumerate() {
+        let line_nr = i + 1;
+        if line.starts_with('#') || line.trim().is_empty() {
+            continue;
+        }
+        if line.starts_with([' ', '\t']) {
+            let (_, text) = current
+                .as_mut()
+                .ok_or(Error::InvalidSource { line: line_nr })?;
+            text.push_str(line.trim_start());
+        } else {
+            if let Some((start, text)) = current.take() {
+                entries.push(parse_entry(&text, start)?);
+            }
+            current = Some((line_nr, line.to_owned()));
+        }
+    }
+    if let Some((start, text)) = current {
+        entries.push(parse_entry(&text, start)?);
+    }
+    Ok(entries)
+}
+
+/// splits the text of an entry at unescaped commas
+fn fields(text: &str) -> impl Iterator<Item = &str> {
+    let bytes = text.as_bytes();
+    let mut start = 0;
+    let mut pos = 0;
+    std::iter::from_fn(move || {
+        while pos < bytes.len() {
+            match bytes[pos] {
+                b'\\' | b'^' => pos += 2,
+                b',' => {
+                    let field = &text[start..pos];
+                    pos += 1;
+                    start = pos;
+                    return Some(field);
+                }
+                _ => pos += 1,
+            }
+        }
+        if start < bytes.len() {
+            let field = &text[start..];
+            start = bytes.len();
+            return Some(field);
+        }
+        None
+    })
+}
+
+fn parse_entry(text: &str, line: usize) -> Result<SourceEntry, Error> {
+    let invalid = || Error::InvalidSource { line };
+    let mut fields = fields(text);
+    let names: Vec<_> = fields
+        .next()
+        .unwrap_or_default()
+        .split('|')
+        .map(|name| name.trim().to_owned())
+        .collect();
+    if names[0].is_empty() {
+        return Err(invalid());
+    }
+
+    let mut capabilities = Vec::new();
+    let mut uses = Vec::new();
+    for field in fields {
+        let field = field.trim_start();
+        // `.cap` comments out a capability
+        if field.trim_end().is_empty() || field.starts_with('.') {
+            continue;
+        }
+        let name_len = field.find(['#', '=', '@']).unwrap_or(field.len());
+        let (name, value) = field.split_at(name_len);
+        let name = name.trim_end();
+        if name.is_empty() || name.contains(char::is_whitespace) {
+            return Err(invalid());
+        }
+        let value = match value.as_bytes().first() {
+            None => SourceValue::True,
+            Some(b'@') if value.trim_end() == "@" => SourceValue::Cancelled,
+            Some(b'#') => {
+                SourceValue::Number(parse_number(value[1..].trim_end()).ok_or_else(invalid)?)
+            }
+            Some(b'=') if name == "use" => {
+                uses.push(value[1..].trim().to_owned());
+                continue;
+            }
+            Some(b'=') => SourceValue::String(unescape(&value[1..])),
+            Some(_) => return Err(invalid()),
+        };
+        capabilities.push((name.to_owned(), value));
+    }
+
+    Ok(SourceEntry {
+        names,
+        capabilities,
+        uses,
+        line,
+    })
+}
+
+/// parses a decimal, octal (leading `0`) or hexadecimal (leading `0x`) number
+fn parse_number(src: &str) -> Option<i32> {
+    let res = if let Some(hex) = src.strip_prefix("0x").or_else(|| src.strip_prefix("0X")) {
+        i32::from_str_radix(hex, 16)
+    } else if src.len() > 1 && src.starts_with('0') {
+        i32::from_str_radix(&src[1..], 8)
+    } else {
+        src.parse()
+    };
+    res.ok().filter(|&num| num >= 0)
+}
+
+/// resolves the escapes (`\E`, `^X`, `\123`, ...) in a string capability
+fn unescape(src: &str) -> Vec<u8> {
+    let mut res = Vec::with_capacity(src.len());
+    let mut bytes = src.bytes();
+    while let Some(c) = bytes.next() {
+        match c {
+            b'^' => match bytes.next() {
+                Some(b'?') => res.push(0x7f),
+                // NUL would terminate the string, tic stores it as \200
+                Some(b'@') => res.push(0o200),
+                Some(c) => res.push(c & 0x1f),
+                None => res.push(b'^'),
+            },
+            b'\\' => {
+                let Some(c) = bytes.next() else {
+                    res.push(b'\\');
+                    break;
+                };
+                let c = match c {
+                    b'E' | b'e' => 0x1b,
+                    b'n' | b'l' => b'\n',
+                    b'r' => b'\r',
+                    b't' => b'\t',
+                    b'b' => 0x08,
+                    b'f' => 0x0c,
+                    b'a' => 0x07,
+                    b's' => b' ',
+                    b'0'..=b'7' => {
+                        let mut val = u32::from(c - b'0');
+                        for _ in 0..2 {
+                            match bytes.clone().next() {
+                                Some(c @ b'0'..=b'7') => {
+                                    val = val * 8 + u32::from(c - b'0');
+                                    bytes.next();
+                                }
+                                _ => break,
+                            }
+                        }
+                        // NUL would terminate the string, tic stores it as \200
+                        match val as u8 {
+                            0 => 0o200,
+                            c => c,
+                        }
+                    }
+                    // `\^`, `\\`, `\,`, `\:` and unknown escapes are the literal char
+                    c => c,
+                };
+                res.push(c)
+            }
+            c => res.push(c),
+        }
+    }
+    res
+}
+
+/// Builds a compiled entry from the names and (fully resolved) capabilities of a source entry
+pub(crate) fn build_terminfo<'a>(
+    names: &[String],
+    capabilities: impl IntoIterator<Item = (&'a str, &'a SourceValue)>,
+) -> Result<TermInfo, Error> {
+    let mut bools = Vec::new();
+    let mut numbers = Vec::new();
+    let mut strings = Vec::new();
+    let mut str_table = Vec::new();
+    let mut extended = BTreeMap::new();
+    let mut ext_table = Vec::new();
+
+    fn set<T: Copy>(slots: &mut Vec<T>, i: usize, absent: T, val: T) {
+        if slots.len() <= i {
+            slots.resize(i + 1, absent);
+        }
+        slots[i] = val;
+    }
+    fn push_str(table: &mut Vec<u8>, val: &[u8]) -> Result<u16, Error> {
+        let off = table.len();
+        table.extend_from_slice(val);
+        table.push(b'\0');
+        match u16::try_from(off) {
+            Ok(off) if table.len() < CANCELLED_STRING as usize => Ok(off),
+            _ => Err(Error::TableTooLarge),
+        }
+    }
+
+    for (name, val) in capabilities {
+        let standard = match val {
+            SourceValue::True => BOOL_NAMES.iter().position(|&it| it == name),
+            SourceValue::Number(_) => NUMBER_NAMES.iter().position(|&it| it == name),
+            SourceValue::String(_) => STRING_NAMES.iter().position(|&it| it == name),
+            SourceValue::Cancelled => None,
+        };
+        match (val, standard) {
+            (SourceValue::True, Some(i)) => set(&mut bools, i, false, true),
+            (&SourceValue::Number(num), Some(i)) => set(&mut numbers, i, ABSENT_NUMBER, num),
+            (SourceValue::String(val), Some(i)) => {
+                let off = push_str(&mut str_table, val)?;
+                set(&mut strings, i, ABSENT_STRING, off)
+            }
+            (SourceValue::Cancelled, _) => {
+                if let Some(i) = NUMBER_NAMES.iter().position(|&it| it == name) {
+                    set(&mut numbers, i, ABSENT_NUMBER, CANCELLED_NUMBER)
+                } else if let Some(i) = STRING_NAMES.iter().position(|&it| it == name) {
+                    set(&mut strings, i, ABSENT_STRING, CANCELLED_STRING)
+                } else if !BOOL_NAMES.contains(&name) {
+                    // cancelled bools are absent in compiled entries
+                    extended.insert(name.to_owned(), ValueStorage::Cancelled);
+                }
+            }
+            (SourceValue::True, None) => {
+                extended.insert(name.to_owned(), ValueStorage::True);
+            }
+            (&SourceValue::Number(num), None) => {
+                extended.insert(name.to_owned(), ValueStorage::Number(num));
+            }
+            (SourceValue::String(val), None) => {
+                let off = push_str(&mut ext_table, val)?;
+                extended.insert(name.to_owned(), ValueStorage::String(off));
+            }
+        }
+    }
+
+    let mut aliases = names[1..].to_vec();
+    Ok(TermInfo {
+        name: names[0].clone(),
+        description: aliases.pop().unwrap_or_default(),
+        aliases,
+        data: TermInfoData {
+            bools: bools.into_boxed_slice(),
+            numbers: numbers.into_boxed_slice(),
+            strings: strings.into_boxed_slice(),
+            str_table: str_table.into_boxed_slice(),
+        },
+        extended: Extended {
+            capabilities: extended,
+            table: ext_table.into_boxed_slice(),
+        },
+        path: None,
+    })
+}
+
+/// A collection of terminfo source entries with `use=` references resolved on demand.
+///
+/// Entries can be loaded from multiple files. If an entry (or one of its aliases) is
+/// defined multiple times, the definition added last takes precedence.
+///
+/// # Example
+/// ```
+/// use termini::{NumberCapability, SourceDatabase};
+///
+/// let mut db = SourceDatabase::new();
+/// db.add_source("base|base terminal,\n\tam, cols#80, lines#24,").unwrap();
+/// db.add_source("derived|derived terminal,\n\tlines#50, am@, use=base,").unwrap();
+/// let info = db.get("derived").unwrap();
+/// assert_eq!(info.number_cap(NumberCapability::Columns), Some(80));
+/// assert_eq!(info.number_cap(NumberCapability::Lines), Some(50));
+/// ```
+#[derive(Debug, Default, Clone)]
+pub struct SourceDatabase {
+    entries: Vec<SourceEntry>,
+    /// maps names and aliases to the index in `entries`
+    names: HashMap<String, usize>,
+}
+
+impl SourceDatabase {
+    pub fn new() -> SourceDatabase {
+        SourceDatabase::default()
+    }
+
+    /// Adds all entries in the terminfo source `src`
+    pub fn add_source(&mut self, src: &str) -> Result<(), Error> {
+        for entry in parse_entries(src)? {
+            let i = self.entries.len();
+            // the last name is the description if there are multiple names
+            let name_cnt = if entry.names.len() > 1 {
+                entry.names.len() - 1
+            } else {
+                1
+            };
+            for name in &entry.names[..name_cnt] {
+                self.names.insert(name.clone(), i);
+            }
+            self.entries.push(entry);
+        }
+        Ok(())
+    }
+
+    /// Adds all entries in the terminfo source file at `path`
+    pub fn add_file(&mut self, path: impl AsRef<Path>) -> Result<(), Error> {
+        self.add_source(&fs::read_to_string(path)?)
+    }
+
+    /// Returns the primary names of all entries in the database in the order they
+    /// were added. Entries redefined by a later entry with the same name are skipped.
+    pub fn names(&self) -> impl Iterator<Item = &str> {
+        (self.entries.iter().enumerate())
+            .filter(|&(i, entry)| self.names.get(&entry.names[0]) == Some(&i))
+            .map(|(_, entry)| entry.names[0].as_str())
+    }
+
+    /// Returns the entry called `name` (or with the alias `name`)
+    /// with all `use=` references resolved
+    pub fn get(&self, name: &str) -> Result<TermInfo, Error> {
+        let &i = (self.names.get(name)).ok_or_else(|| NotFound::new(name))?;
+        let capabilities = self.resolve(i, &mut Vec::new())?;
+        build_terminfo(
+            &self.entries[i].names,
+            capabilities
+                .iter()
+                .filter_map(|(name, val)| Some((name.as_str(), val.as_ref()?))),
+        )
+    }
+
+    /// Flattens the capabilities of `entry` and the entries it references like `tic`:
+    /// the rightmost `use=` is merged first, capabilities of the entry itself last.
+    /// Merging a cancelled capability of a referenced entry removes it,
+    /// cancelled capabilities of the entry itself are retained.
+    fn resolve(
+        &self,
+        entry: usize,
+        stack: &mut Vec<usize>,
+    ) -> Result<Vec<(String, Option<SourceValue>)>, Error> {
+        let src = &self.entries[entry];
+        if src.uses.is_empty() {
+            return Ok(src
+                .capabilities
+                .iter()
+                .map(|(name, val)| (name.clone(), Some(val.clone())))
+                .collect());
+        }
+        if stack.contains(&entry) {
+            return Err(Error::UseCycle(src.names[0].clone()));
+        }
+        stack.push(entry);
+
+        let mut merged: Vec<(String, Option<SourceValue>)> = Vec::new();
+        let mut positions = HashMap::new();
+        let mut merge = |name: &str, val: Option<SourceValue>| match positions.get(name) {
+            Some(&i) => merged[i] = (name.to_owned(), val),
+            None => {
+                positions.insert(name.to_owned(), merged.len());
+                merged.push((name.to_owned(), val));
+            }
+        };
+        for name in src.uses.iter().rev() {
+            let &base = self
+                .names
+                .get(name)
+                .ok_or_else(|| Error::MissingUse(name.clone()))?;
+            for (name, val) in self.resolve(base, stack)? {
+                if val.is_some() {
+                    merge(&name, val.filter(|val| *val != SourceValue::Cancelled));
+                }
+            }
+        }
+        for (name, val) in &src.capabilities {
+            merge(name, Some(val.clone()));
+        }
+
+        stack.pop();
+        Ok(merged)
+    }
+}
+
+impl TermInfo {
+    /// Parses the first entry of the terminfo source `src`
+    /// (the text format read by `tic` and written by `infocmp`).
+    /// `use=` references are resolved with the other entries in `src`,
+    /// use [`SourceDatabase`] to resolve them across multiple files.
+    ///
+    /// Standard capabilities are stored as [`BoolCapability`](crate::BoolCapability),
+    /// [`NumberCapability`](crate::NumberCapability) and
+    /// [`StringCapability`](crate::StringCapability), all other capabilities are
+    /// available as extended capabilities.
+    ///
+    /// # Example
+    /// ```
+    /// use termini::{NumberCapability, TermInfo, Value};
+    ///
+    /// let src = "\
+    /// ## local override
+    /// myterm|my terminal,
+    ///     am, colors#256,
+    ///     cup=\\E[%i%p1%d;%p2%dH, Smulx=\\E[4:%p1%dm,
+    /// ";
+    /// let info = TermInfo::from_source(src).unwrap();
+    /// assert_eq!(info.name, "myterm");
+    /// assert_eq!(info.number_cap(NumberCapability::MaxColors), Some(256));
+    /// assert_eq!(info.extended_cap("Smulx"), Some(Value::Utf8String("\x1b[4:%p1%dm")));
+    /// ```
+    pub fn from_source(src: &str) -> Result<TermInfo, Error> {
+        let mut db = SourceDatabase::new();
+        db.add_source(src)?;
+        let entry = db.entries.first().ok_or(Error::NoNames)?;
+        let capabilities = db.resolve(0, &mut Vec::new())?;
+        build_terminfo(
+            &entry.names,
+            capabilities
+                .iter()
+                .filter_map(|(name, val)| Some((name.as_str(), val.as_ref()?))),
+        )
+    }
+}
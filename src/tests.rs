@@ -1,6 +1,56 @@
 use std::fs;
+use std::ops::Deref;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::Duration;
 
-use crate::{BoolCapability, NumberCapability, StringCapability, TermInfo, Value};
+use crate::{
+    expand, expand_to_fmt, is_valid_name, BoolCapability, Capability, Difference, DumpOptions,
+    Error, Fallbacks, Format, Layout, NumberCapability, Param, Program, SearchPath, SortOrder,
+    SourceDatabase, State, StringCapability, TermInfo, TermInfoRef, Value, ValueStorage, Variables,
+};
+
+/// Returns the paths of the compiled entries in `tests/`
+fn fixtures() -> impl Iterator<Item = PathBuf> {
+    (fs::read_dir("tests").unwrap())
+        .map(|f| f.unwrap().path())
+        .filter(|path| path.is_file())
+}
+
+/// An empty temporary directory that is removed when it's dropped
+struct TempDir(PathBuf);
+
+impl TempDir {
+    fn new(name: &str) -> TempDir {
+        // unique within the process as tests run in parallel
+        static COUNT: AtomicUsize = AtomicUsize::new(0);
+        let count = COUNT.fetch_add(1, Ordering::Relaxed);
+        let name = format!("termini-{name}-{}-{count}", std::process::id());
+        let path = std::env::temp_dir().join(name);
+        let _ = fs::remove_dir_all(&path);
+        fs::create_dir_all(&path).unwrap();
+        TempDir(path)
+    }
+}
+
+impl Deref for TempDir {
+    type Target = Path;
+    fn deref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl AsRef<Path> for TempDir {
+    fn as_ref(&self) -> &Path {
+        &self.0
+    }
+}
+
+impl Drop for TempDir {
+    fn drop(&mut self) {
+        let _ = fs::remove_dir_all(&self.0);
+    }
+}
 
 #[test]
 fn name() {
@@ -62,6 +112,17 @@ fn kitty_extended_underculr() {
     assert_eq!(Some(Value::True), db.extended_cap("Su"));
 }
 
+#[test]
+fn extended_cancelled_string() {
+    let db = TermInfo::from_path("tests/screen.xterm-new").unwrap();
+    assert_eq!(Some(Value::True), db.extended_cap("XT"));
+    assert_eq!(
+        Some(Value::Utf8String("\u{1b}]52;%p1%s;%p2%s\u{7}")),
+        db.extended_cap("Ms")
+    );
+    assert_eq!(None, db.extended_cap("kNXT"));
+}
+
 #[test]
 fn crash() {
     let data: &[u8] = &[
@@ -73,7 +134,1174 @@ fn crash() {
 
 #[test]
 fn test_parse() {
-    for f in fs::read_dir("tests/").unwrap() {
-        let _ = TermInfo::from_path(f.unwrap().path()).unwrap();
+    for path in fixtures() {
+        let _ = TermInfo::from_path(path).unwrap();
+    }
+}
+
+#[test]
+fn expand_cursor_address() {
+    let db = TermInfo::from_path("tests/xterm-256color").unwrap();
+    let mut vars = Variables::new();
+    let res = db.expand(
+        StringCapability::CursorAddress,
+        &[Param::Number(9), Param::Number(19)],
+        &mut vars,
+    );
+    assert_eq!(b"\x1b[10;20H", &*res.unwrap());
+    let res = db.expand(
+        StringCapability::SetAnsiForeground,
+        &[Param::Number(200)],
+        &mut vars,
+    );
+    assert_eq!(b"\x1b[38;5;200m", &*res.unwrap());
+    let res = db.expand(
+        StringCapability::SetAnsiForeground,
+        &[Param::Number(9)],
+        &mut vars,
+    );
+    assert_eq!(b"\x1b[91m", &*res.unwrap());
+}
+
+#[test]
+fn expand_formats() {
+    let mut vars = Variables::new();
+    let mut check = |template: &str, params: &[Param], expected: &str| {
+        let res = expand(template.as_bytes(), params, &mut vars).unwrap();
+        assert_eq!(expected, String::from_utf8(res).unwrap(), "{template}");
+    };
+    check("%p1%:-5d|", &[42.into()], "42   |");
+    check("%p1%5.3d", &[7.into()], "  007");
+    check("%p1%03d", &[(-7).into()], "-07");
+    check("%p1%#x %p1%#o %p1%X", &[255.into()], "0xff 0377 FF");
+    check("%p1%:-4s|%p1%.2s", &["abc".into()], "abc |ab");
+    check("%p1%l%d", &["hello".into()], "5");
+    check("%'A'%p1%+%c", &[2.into()], "C");
+    check(
+        "%p1%p2%m%d %p1%p2%/%d %p1%~%d",
+        &[7.into(), 3.into()],
+        "1 2 -8",
+    );
+    check("%p1%PA%gA%gA%*%d", &[6.into()], "36");
+    check("%gA%d", &[], "6");
+    check("%%%d;%d", &[1.into(), 2.into()], "%1;2");
+}
+
+#[test]
+fn expand_conditionals() {
+    let template = b"%?%p1%{8}%<%t3%p1%d%e%p1%{16}%<%t9%p1%{8}%-%d%e38;5;%p1%d%;";
+    let mut vars = Variables::new();
+    let mut check = |param: i32, expected: &[u8]| {
+        assert_eq!(
+            expected,
+            &*expand(template, &[param.into()], &mut vars).unwrap()
+        );
+    };
+    check(1, b"31");
+    check(12, b"94");
+    check(100, b"38;5;100");
+    let nested = b"%?%p1%t%?%p2%tA%eB%;%eC%;";
+    for (p1, p2, expected) in [(1, 1, b"A"), (1, 0, b"B"), (0, 1, b"C")] {
+        let res = expand(nested, &[p1.into(), p2.into()], &mut vars).unwrap();
+        assert_eq!(expected, &*res);
+    }
+}
+
+#[test]
+fn expand_errors() {
+    let mut vars = Variables::new();
+    assert!(matches!(
+        expand(b"ab%p0%d", &[], &mut vars),
+        Err(Error::InvalidTemplate { offset: 2 })
+    ));
+    assert!(matches!(
+        expand(b"%p1%+%d", &[1.into()], &mut vars),
+        Err(Error::StackUnderflow)
+    ));
+    assert!(matches!(
+        expand(b"%p1%d", &["x".into()], &mut vars),
+        Err(Error::TypeMismatch)
+    ));
+    let db = TermInfo::from_path("tests/xterm-256color").unwrap();
+    assert!(matches!(
+        db.expand(StringCapability::ZeroMotion, &[], &mut vars),
+        Err(Error::MissingCapability)
+    ));
+}
+
+#[test]
+fn expand_to_writer() {
+    let db = TermInfo::from_path("tests/alacritty").unwrap();
+    let mut vars = Variables::new();
+    let mut out = Vec::new();
+    db.expand_to(
+        StringCapability::CursorAddress,
+        &[0.into(), 0.into()],
+        &mut vars,
+        &mut out,
+    )
+    .unwrap();
+    db.expand_extended_to("Smulx", &[3.into()], &mut vars, &mut out)
+        .unwrap();
+    assert_eq!(b"\x1b[1;1H\x1b[4:3m", &*out);
+    assert!(matches!(
+        db.expand_extended_to("Su", &[], &mut vars, &mut out),
+        Err(Error::MissingCapability)
+    ));
+
+    let mut out = String::new();
+    expand_to_fmt(b"\x1b[%p1%dm", &[31.into()], &mut vars, &mut out).unwrap();
+    assert_eq!("\x1b[31m", out);
+    assert!(matches!(
+        expand_to_fmt(b"%p1%c", &[200.into()], &mut vars, &mut out),
+        Err(Error::Fmt(_))
+    ));
+
+    let mut out = String::new();
+    db.expand_to_fmt(
+        StringCapability::CursorAddress,
+        &[0.into(), 0.into()],
+        &mut vars,
+        &mut out,
+    )
+    .unwrap();
+    db.expand_extended_to_fmt("Smulx", &[3.into()], &mut vars, &mut out)
+        .unwrap();
+    assert_eq!("\x1b[1;1H\x1b[4:3m", out);
+    let absent = (StringCapability::ALL.iter().copied())
+        .find(|&cap| db.raw_string_cap(cap).is_none())
+        .unwrap();
+    assert!(matches!(
+        db.expand_to_fmt(absent, &[], &mut vars, &mut out),
+        Err(Error::MissingCapability)
+    ));
+    assert!(matches!(
+        db.expand_extended_to_fmt("Su", &[], &mut vars, &mut out),
+        Err(Error::MissingCapability)
+    ));
+}
+
+#[test]
+fn compiled_program() {
+    let mut vars = Variables::new();
+    for path in fixtures() {
+        let db = TermInfo::from_path(path).unwrap();
+        for cap in [
+            StringCapability::CursorAddress,
+            StringCapability::SetAnsiForeground,
+        ] {
+            let Ok(program) = db.compile(cap) else {
+                continue;
+            };
+            for param in [0, 7, 15, 255] {
+                let params = [param.into(), 3.into()];
+                assert_eq!(
+                    db.expand(cap, &params, &mut vars).unwrap(),
+                    program.expand(&params, &mut vars).unwrap()
+                );
+            }
+        }
+    }
+
+    let program = Program::compile(b"%?%p1%t%?%p2%tA%eB%;%e%p3%tC%eD%;").unwrap();
+    for (params, expected) in [
+        ([1, 1, 0], "A"),
+        ([1, 0, 1], "B"),
+        ([0, 1, 1], "C"),
+        ([0, 0, 0], "D"),
+    ] {
+        let params = params.map(Param::Number);
+        let mut out = String::new();
+        program.expand_to_fmt(&params, &mut vars, &mut out).unwrap();
+        assert_eq!(expected, out);
+    }
+
+    for (template, offset) in [
+        ("ab%?%p1%tc", 2),
+        ("%p1%;", 3),
+        ("%?%p1%ta%e%e%;", 10),
+        ("%p1%Q", 3),
+    ] {
+        let err = Program::compile(template.as_bytes()).unwrap_err();
+        assert!(
+            matches!(err, Error::InvalidTemplate { offset: off } if off == offset),
+            "{template}"
+        );
+    }
+}
+
+#[test]
+fn padding() {
+    let mut delays = Vec::new();
+    let mut out = Vec::new();
+    // xon/xoff: padding is stripped
+    let db = TermInfo::from_path("tests/vt100").unwrap();
+    let clear = db.raw_string_cap(StringCapability::ClearScreen).unwrap();
+    db.tputs(clear, 1, 9600, &mut out, |delay| delays.push(delay))
+        .unwrap();
+    assert_eq!(b"\x1b[H\x1b[J", &*out);
+
+    // no pad char: mandatory delay is reported to the callback
+    out.clear();
+    let db = TermInfo::from_path("tests/xterm-256color").unwrap();
+    let flash = db.raw_string_cap(StringCapability::FlashScreen).unwrap();
+    db.tputs(flash, 1, 9600, &mut out, |delay| delays.push(delay))
+        .unwrap();
+    assert_eq!(b"\x1b[?5h\x1b[?5l", &*out);
+    assert_eq!(vec![Duration::from_millis(100)], delays);
+
+    // pad with NUL: 100ms at 9600 baud
+    out.clear();
+    let db = TermInfo::from_path("tests/vte").unwrap();
+    db.tputs(b"a$<100/>b$<2.5*>c$<x>", 4, 9600, &mut out, |delay| {
+        delays.push(delay)
+    })
+    .unwrap();
+    let mut expected = b"a".to_vec();
+    expected.extend([0; 106]);
+    expected.push(b'b');
+    expected.extend([0; 10]);
+    expected.extend(b"c$<x>");
+    assert_eq!(expected, out);
+    assert_eq!(1, delays.len());
+}
+
+/// asserts that `a` and `b` contain the same capabilities
+fn assert_same_caps(a: &TermInfo, b: &TermInfo) {
+    assert_eq!(a.name, b.name);
+    assert_eq!(a.aliases, b.aliases);
+    assert_eq!(a.description, b.description);
+    let len = a.data.bools.len().max(b.data.bools.len());
+    for i in 0..len {
+        let get = |db: &TermInfo| db.data.bools.get(i).copied().unwrap_or(false);
+        assert_eq!(get(a), get(b), "{}: bool {i}", a.name);
+    }
+    let len = a.data.numbers.len().max(b.data.numbers.len());
+    for i in 0..len {
+        let get = |db: &TermInfo| db.data.numbers.get(i).copied().filter(|&num| num >= 0);
+        assert_eq!(get(a), get(b), "{}: number {i}", a.name);
+    }
+    let len = a.data.strings.len().max(b.data.strings.len());
+    for i in 0..len {
+        let get = |db: &TermInfo| {
+            let off = *db.data.strings.get(i)?;
+            db.data.get_str_at(off).map(<[u8]>::to_vec)
+        };
+        assert_eq!(get(a), get(b), "{}: string {i}", a.name);
+    }
+    // cancelled capabilities are compared like absent capabilities
+    fn present<'a>((name, val): (&'a String, &ValueStorage)) -> Option<&'a String> {
+        (*val != ValueStorage::Cancelled).then_some(name)
+    }
+    let mut names: Vec<_> = a.extended.capabilities.iter().filter_map(present).collect();
+    let mut other: Vec<_> = b.extended.capabilities.iter().filter_map(present).collect();
+    names.sort();
+    other.sort();
+    assert_eq!(names, other);
+    for name in names {
+        assert_eq!(
+            a.extended_cap(name),
+            b.extended_cap(name),
+            "{}: {name}",
+            a.name
+        );
+    }
+}
+
+#[test]
+fn source() {
+    for f in fs::read_dir("tests/source").unwrap() {
+        let path = f.unwrap().path();
+        if !path.is_file() {
+            continue;
+        }
+        let src = fs::read_to_string(&path).unwrap();
+        let db = TermInfo::from_source(&src).unwrap();
+        let compiled = TermInfo::from_path(format!("tests/{}", db.name)).unwrap();
+        assert_same_caps(&db, &compiled);
     }
 }
+
+#[test]
+fn source_syntax() {
+    let src = "\
+# comment
+test|alias|test terminal,
+\tam, .bw, cols#0x50, lines#030, it#8,
+\tbel=^G, cr=\\r, ht=\\011, kf1=\\EOP\\,\\:, kf2=^[^?\\s\\0, kf3=a^@b,
+\tkcub1@, Ext, Ext2#12, Ext3=\\E[%p1%dq,
+
+other|other terminal, am,
+";
+    let db = TermInfo::from_source(src).unwrap();
+    assert_eq!("test", db.name);
+    assert_eq!(vec!["alias"], db.aliases);
+    assert_eq!("test terminal", db.description);
+    assert!(db.flag_cap(BoolCapability::AutoRightMargin));
+    assert!(!db.flag_cap(BoolCapability::AutoLeftMargin));
+    assert_eq!(Some(80), db.number_cap(NumberCapability::Columns));
+    assert_eq!(Some(24), db.number_cap(NumberCapability::Lines));
+    assert_eq!(
+        Some(&b"\x07"[..]),
+        db.raw_string_cap(StringCapability::Bell)
+    );
+    assert_eq!(
+        Some(&b"\r"[..]),
+        db.raw_string_cap(StringCapability::CarriageReturn)
+    );
+    assert_eq!(Some(&b"\t"[..]), db.raw_string_cap(StringCapability::Tab));
+    assert_eq!(
+        Some(&b"\x1bOP,:"[..]),
+        db.raw_string_cap(StringCapability::KeyF1)
+    );
+    assert_eq!(
+        Some(&b"\x1b\x7f \x80"[..]),
+        db.raw_string_cap(StringCapability::KeyF2)
+    );
+    assert_eq!(
+        Some(&b"a\x80b"[..]),
+        db.raw_string_cap(StringCapability::KeyF3)
+    );
+    assert_eq!(None, db.raw_string_cap(StringCapability::KeyLeft));
+    assert_eq!(Some(Value::True), db.extended_cap("Ext"));
+    assert_eq!(Some(Value::Number(12)), db.extended_cap("Ext2"));
+    assert_eq!(
+        Some(Value::Utf8String("\x1b[%p1%dq")),
+        db.extended_cap("Ext3")
+    );
+
+    assert!(matches!(
+        TermInfo::from_source("test,\n\tcols#abc,"),
+        Err(Error::InvalidSource { line: 1 })
+    ));
+    assert!(matches!(
+        TermInfo::from_source("\tam,"),
+        Err(Error::InvalidSource { line: 1 })
+    ));
+    assert!(matches!(
+        TermInfo::from_source("test, use=xterm,"),
+        Err(Error::MissingUse(name)) if name == "xterm"
+    ));
+}
+
+#[test]
+fn source_use() {
+    let mut db = SourceDatabase::new();
+    db.add_file("tests/source/screen.ti").unwrap();
+    let mut files: Vec<_> = fs::read_dir("tests/source/uses")
+        .unwrap()
+        .map(|f| f.unwrap().path())
+        .collect();
+    files.sort();
+    for path in files {
+        db.add_file(path).unwrap();
+    }
+    // in the order of the definitions
+    assert_eq!(
+        [
+            "screen",
+            "tmux-256color",
+            "screen-256color",
+            "vt100",
+            "xterm"
+        ],
+        *db.names().collect::<Vec<_>>()
+    );
+    // redefined entries keep the position of the last definition
+    let mut redefined = SourceDatabase::new();
+    redefined
+        .add_source("a|A, am,\nb|B, am,\nc|C, am,\na|A again, am,")
+        .unwrap();
+    assert_eq!(["b", "c", "a"], *redefined.names().collect::<Vec<_>>());
+    for name in ["vt100-am", "tmux-256color", "screen-256color"] {
+        let info = db.get(name).unwrap();
+        let compiled = TermInfo::from_path(format!("tests/{}", info.name)).unwrap();
+        assert_same_caps(&info, &compiled);
+    }
+
+    let src = "a|A,\n\tam, cols#80, bel=^G, use=b,\nb|B,\n\tcols#100, cr=^M, use=a,";
+    assert!(matches!(TermInfo::from_source(src), Err(Error::UseCycle(name)) if name == "a"));
+    let src = "a|A,\n\tam, use=b,";
+    assert!(matches!(TermInfo::from_source(src), Err(Error::MissingUse(name)) if name == "b"));
+    assert!(matches!(db.get("xterm-256color"), Err(Error::NotFound(_))));
+
+    // the leftmost use= takes precedence, cancellations remove inherited capabilities
+    let src = "a|A,\n\tcr@, use=b, use=c,\nb|B,\n\tcols#80, kf1@,\nc|C,\n\tcols#100, cr=^M, kf1=^A, lines#24,";
+    let info = TermInfo::from_source(src).unwrap();
+    assert_eq!(Some(80), info.number_cap(NumberCapability::Columns));
+    assert_eq!(Some(24), info.number_cap(NumberCapability::Lines));
+    assert_eq!(None, info.raw_string_cap(StringCapability::CarriageReturn));
+    assert_eq!(None, info.raw_string_cap(StringCapability::KeyF1));
+    // like tic, only cancellations of the entry itself are retained
+    let cr = StringCapability::CarriageReturn;
+    assert_eq!(State::Cancelled, info.string_cap_state(cr));
+    assert_eq!(
+        State::Absent,
+        info.string_cap_state(StringCapability::KeyF1)
+    );
+}
+
+#[test]
+fn cancelled_caps() {
+    let db = TermInfo::from_path("tests/Eterm").unwrap();
+    let ncv = NumberCapability::NoColorVideo;
+    assert_eq!(State::Cancelled, db.number_cap_state(ncv));
+    assert_eq!(None, db.number_cap(ncv));
+    let cols = NumberCapability::Columns;
+    assert_eq!(State::Present(Value::Number(80)), db.number_cap_state(cols));
+    assert_eq!(
+        State::Absent,
+        db.number_cap_state(NumberCapability::MagicCookieGlitch)
+    );
+    let knp = StringCapability::KeyNextPage;
+    assert_eq!(
+        State::Present(Value::Utf8String("\u{1b}[6~")),
+        db.string_cap_state(knp)
+    );
+    assert_eq!(
+        State::Cancelled,
+        db.string_cap_state(StringCapability::KeyShiftNext)
+    );
+
+    let db = TermInfo::from_path("tests/screen.xterm-new").unwrap();
+    assert_eq!(State::Cancelled, db.extended_cap_state("E3"));
+    assert_eq!(State::Absent, db.extended_cap_state("E4"));
+    assert_eq!(None, db.extended_cap("E3"));
+    assert!(db.extended_caps().all(|(name, _)| name != "E3"));
+
+    // cancelled extended capabilities of all types are written as strings
+    let src = "test|test, Ext@, Xn@, Xs@, Yz,\n";
+    let db = TermInfo::from_source(src).unwrap();
+    let mut compiled = Vec::new();
+    db.write_to(&mut compiled).unwrap();
+    let db = TermInfo::parse(&*compiled).unwrap();
+    for name in ["Ext", "Xn", "Xs"] {
+        assert_eq!(State::Cancelled, db.extended_cap_state(name));
+    }
+    assert_eq!(State::Present(Value::True), db.extended_cap_state("Yz"));
+    assert!(db
+        .dump(DumpOptions::default())
+        .to_string()
+        .contains("Ext@, Xn@, Xs@,"));
+}
+
+#[test]
+fn write_round_trip() {
+    for path in fixtures() {
+        let original = fs::read(&path).unwrap();
+        let db = TermInfo::parse(&*original).unwrap();
+        let mut compiled = Vec::new();
+        db.write_to(&mut compiled).unwrap();
+        assert_eq!(original[..2], compiled[..2], "{}: format", db.name);
+        let reparsed = TermInfo::parse(&*compiled).unwrap();
+        assert_same_caps(&db, &reparsed);
+        let mut again = Vec::new();
+        reparsed.write_to(&mut again).unwrap();
+        assert_eq!(compiled, again, "{}", db.name);
+    }
+
+    let db = TermInfo::from_path("tests/xterm-direct").unwrap();
+    let mut compiled = Vec::new();
+    db.write_to_format(&mut compiled, Format::Legacy).unwrap();
+    let legacy = TermInfo::parse(&*compiled).unwrap();
+    assert_eq!(db.number_cap(NumberCapability::MaxColors), Some(0x1000000));
+    assert_eq!(legacy.number_cap(NumberCapability::MaxColors), Some(0x7fff));
+
+    let db = TermInfo::from_source("test|test terminal, cols#80, kfoo=\\EOP, Tc,").unwrap();
+    let mut compiled = Vec::new();
+    db.write_to(&mut compiled).unwrap();
+    assert_same_caps(&db, &TermInfo::parse(&*compiled).unwrap());
+    assert!(TermInfo::default().write_to(&mut Vec::new()).is_err());
+
+    // an empty description is written so the last alias isn't read as the description
+    let mut db = TermInfo::from_source("test|alias1|alias2|, am,").unwrap();
+    assert_eq!(["alias1", "alias2"], *db.aliases);
+    assert_eq!("", db.description);
+    let mut compiled = Vec::new();
+    db.write_to(&mut compiled).unwrap();
+    let reparsed = TermInfoRef::parse(&compiled).unwrap();
+    assert_eq!(
+        ["alias1", "alias2"],
+        *reparsed.aliases().collect::<Vec<_>>()
+    );
+    assert_eq!("", reparsed.description);
+    let dumped = TermInfo::from_source(&db.dump(DumpOptions::default()).to_string()).unwrap();
+    assert_eq!(db.aliases, dumped.aliases);
+    db.aliases.clear();
+    let mut compiled = Vec::new();
+    db.write_to(&mut compiled).unwrap();
+    assert_eq!("test", TermInfoRef::parse(&compiled).unwrap().names);
+}
+
+#[test]
+fn install() {
+    let dir = TempDir::new("install");
+    let db = TermInfo::from_path("tests/st-256color").unwrap();
+    let path = db.install(&dir).unwrap();
+    assert_eq!(dir.join("s/st-256color"), path);
+    let installed = TermInfo::from_path(dir.join("s/stterm-256color")).unwrap();
+    assert_same_caps(&db, &installed);
+    // reinstalling replaces the existing entry
+    db.install(&dir).unwrap();
+
+    let path = db.install_with_layout(&dir, Layout::Hex).unwrap();
+    assert_eq!(dir.join("73/st-256color"), path);
+    assert!(dir.join("73/stterm-256color").is_file());
+
+    // names that can't be looked up are rejected before anything is written
+    for name in ["a/b", "..", ".", "x\0", "a\\b", "a b", ""] {
+        let empty = TempDir::new("install-invalid");
+        let mut invalid = TermInfo::from_path("tests/st-256color").unwrap();
+        invalid.aliases.push(name.to_owned());
+        assert!(matches!(
+            invalid.install(&empty),
+            Err(Error::InvalidTermName(invalid)) if invalid == name
+        ));
+        invalid.aliases.clear();
+        invalid.name = name.to_owned();
+        assert!(matches!(
+            invalid.install_with_layout(&empty, Layout::Hex),
+            Err(Error::InvalidTermName(_) | Error::NoNames)
+        ));
+        assert_eq!(0, fs::read_dir(&*empty).unwrap().count());
+    }
+}
+
+#[test]
+fn dump() {
+    for f in fs::read_dir("tests/source").unwrap() {
+        let path = f.unwrap().path();
+        if !path.is_file() {
+            continue;
+        }
+        let src = fs::read_to_string(&path).unwrap();
+        let db = TermInfo::from_path(format!(
+            "tests/{}",
+            path.file_stem().unwrap().to_str().unwrap()
+        ))
+        .unwrap();
+        let one_per_line = DumpOptions {
+            width: 0,
+            ..DumpOptions::default()
+        };
+        // skip the leading comment added by infocmp
+        let expected = src.split_once('\n').unwrap().1;
+        assert_eq!(expected, db.dump(one_per_line).to_string(), "{}", db.name);
+    }
+
+    for path in fixtures() {
+        let db = TermInfo::from_path(path).unwrap();
+        for sort in [SortOrder::Name, SortOrder::Database] {
+            let options = DumpOptions {
+                sort,
+                ..DumpOptions::default()
+            };
+            let src = db.dump(options).to_string();
+            assert!(src.lines().skip(1).all(|line| line.starts_with('\t')));
+            // acsc is sorted so the text is compared instead of the capabilities
+            let reparsed = TermInfo::from_source(&src).unwrap();
+            assert_eq!(src, reparsed.dump(options).to_string());
+        }
+    }
+
+    let db =
+        TermInfo::from_source("test|test terminal, am, cols#0x100, xx=^A\\s\\,\\200:\\s,").unwrap();
+    let options = DumpOptions {
+        width: 25,
+        ..DumpOptions::default()
+    };
+    assert_eq!(
+        "test|test terminal,\n\tam,\n\tcols#0x100,\n\txx=\\001 \\,\\0:\\s,\n",
+        db.dump(options).to_string()
+    );
+
+    let db = TermInfo::from_source("t|t, colors#2147483647,").unwrap();
+    assert_eq!(
+        "t|t,\n\tcolors#0x7fffffff,\n",
+        db.dump(DumpOptions::default()).to_string()
+    );
+}
+
+#[test]
+fn capability_metadata() {
+    let cup = StringCapability::CursorAddress;
+    assert_eq!("cup", cup.name());
+    assert_eq!("cursor_address", cup.long_name());
+    assert_eq!("cm", cup.termcap_name());
+    assert_eq!("Move to row #1 col #2", cup.description());
+    assert!(matches!(
+        StringCapability::from_long_name("cursor_address"),
+        Some(StringCapability::CursorAddress)
+    ));
+    assert!(matches!(
+        StringCapability::from_termcap_name("cm"),
+        Some(StringCapability::CursorAddress)
+    ));
+    // `dl` is delete_line in termcap but parm_delete_line in terminfo
+    assert!(matches!(
+        StringCapability::from_termcap_name("dl"),
+        Some(StringCapability::DeleteLine)
+    ));
+    assert!(matches!(
+        StringCapability::from_name("dl"),
+        Some(StringCapability::ParmDeleteLine)
+    ));
+    assert_eq!("colors", NumberCapability::MaxColors.name());
+    assert_eq!("xon", BoolCapability::XonXoff.name());
+    assert_eq!("xo", BoolCapability::XonXoff.termcap_name());
+    assert!(StringCapability::from_name("cursor_address").is_none());
+    assert!(BoolCapability::from_name("OTbs").is_none());
+
+    for (i, &cap) in StringCapability::ALL.iter().enumerate() {
+        assert_eq!(Some(cap), StringCapability::from_name(cap.name()));
+        assert_eq!(Some(cap), StringCapability::from_long_name(cap.long_name()));
+        assert_eq!(i, cap as usize);
+    }
+}
+
+#[test]
+fn capability_enums() {
+    assert_eq!(37, BoolCapability::ALL.len());
+    assert_eq!(33, NumberCapability::ALL.len());
+    assert_eq!(394, StringCapability::ALL.len());
+    let caps: std::collections::HashSet<_> = StringCapability::ALL.iter().copied().collect();
+    assert_eq!(394, caps.len());
+    assert!(StringCapability::CursorAddress < StringCapability::SetPageLenInch);
+    assert_eq!("MaxColors", format!("{:?}", NumberCapability::MaxColors));
+    assert_eq!(
+        BoolCapability::AutoRightMargin,
+        BoolCapability::try_from(1).unwrap()
+    );
+    assert!(matches!(
+        NumberCapability::try_from(33),
+        Err(Error::InvalidCapabilityIndex(33))
+    ));
+}
+
+#[test]
+fn get_by_name() {
+    let db = TermInfo::from_path("tests/xterm-256color").unwrap();
+    assert_eq!(Some(Value::True), db.get("am"));
+    assert_eq!(None, db.get("bw"));
+    assert_eq!(Some(Value::Number(256)), db.get("colors"));
+    assert_eq!(Some(Value::Number(256)), db.get("Co"));
+    assert_eq!(
+        Some(Value::Utf8String("\u{1b}[%i%p1%d;%p2%dH")),
+        db.get("cup")
+    );
+    assert_eq!(db.get("cup"), db.get("cm"));
+    assert_eq!(Some(Value::True), db.get("AX"));
+    // terminfo names take precedence over termcap names
+    assert_eq!(Some(Value::Utf8String("\u{1b}[%p1%dM")), db.get("dl"));
+    assert_eq!(None, db.get("cursor_address"));
+}
+
+#[test]
+fn iterate_caps() {
+    let db = TermInfo::from_path("tests/xterm-256color").unwrap();
+    let bools: Vec<_> = db.bool_caps().map(|(cap, _)| cap.name()).collect();
+    assert_eq!(
+        ["am", "xenl", "km", "mir", "msgr", "mc5i", "npc", "ccc", "bce"],
+        *bools
+    );
+    let numbers: Vec<_> = db.number_caps().collect();
+    assert!(numbers
+        .iter()
+        .all(|(cap, val)| db.get(cap.name()) == Some(*val)));
+    assert_eq!(5, numbers.len());
+    for (cap, val) in db.string_caps() {
+        assert_eq!(Some(val), db.get(cap.name()));
+    }
+    // obsolete termcap capabilities are stored after the standard capabilities
+    let strings = &db.data.strings[..StringCapability::SetPageLenInch as usize + 1];
+    let present = strings.iter().filter(|&&off| off < 0xfffe).count();
+    assert_eq!(present, db.string_caps().count());
+
+    let names: Vec<_> = db.extended_caps().map(|(name, _)| name).collect();
+    let mut sorted = names.clone();
+    sorted.sort_unstable();
+    assert_eq!(sorted, names);
+    assert!(names.contains(&"XM"));
+    assert!(db
+        .extended_caps()
+        .all(|(name, val)| db.extended_cap(name) == Some(val)));
+    // absent extended numbers are skipped
+    let db = TermInfo::from_path("tests/xterm").unwrap();
+    assert!(db
+        .extended_caps()
+        .all(|(_, val)| !matches!(val, Value::Number(_))));
+}
+
+#[test]
+fn diff() {
+    let kitty = TermInfo::from_path("tests/xterm-kitty").unwrap();
+    let tmux = TermInfo::from_path("tests/tmux-256color").unwrap();
+    assert!(kitty.diff(&kitty).is_empty());
+    let diff = kitty.diff(&tmux);
+    // infocmp -x -d xterm-kitty tmux-256color without obsolete termcap capabilities
+    assert_eq!(65, diff.len());
+    assert_eq!(
+        Difference {
+            capability: Capability::Bool(BoolCapability::PrtrSilent),
+            left: State::Present(Value::True),
+            right: State::Absent,
+        },
+        diff[0]
+    );
+    let find = |name: &str| *diff.iter().find(|it| it.capability.name() == name).unwrap();
+    assert_eq!(State::Present(Value::Number(32767)), find("pairs").left);
+    assert_eq!(State::Present(Value::Number(65536)), find("pairs").right);
+    assert_eq!(State::Absent, find("U8").left);
+    assert_eq!(
+        State::Present(Value::Utf8String("\u{1b}[H\u{1b}[J")),
+        find("clear").right
+    );
+    assert!(diff.iter().all(|it| it.left != it.right));
+
+    let eterm = TermInfo::from_path("tests/Eterm").unwrap();
+    let xterm = TermInfo::from_path("tests/xterm").unwrap();
+    let diff = eterm.diff(&xterm);
+    let cancelled: Vec<_> = diff
+        .iter()
+        .filter(|it| it.left == State::Cancelled)
+        .map(|it| it.capability.to_string())
+        .collect();
+    assert_eq!(["ncv", "kNXT", "kPRV"], *cancelled);
+}
+
+#[test]
+fn numbers_32bit() {
+    let db = TermInfo::from_path("tests/termini-32bit").unwrap();
+    let num = |cap| db.number_cap(cap);
+    assert_eq!(Some(0x1000000), num(NumberCapability::MaxColors));
+    assert_eq!(Some(0xffff), num(NumberCapability::MaxPairs));
+    assert_eq!(Some(0), num(NumberCapability::InitTabs));
+    assert_eq!(None, num(NumberCapability::Lines));
+    assert_eq!(
+        State::Cancelled,
+        db.number_cap_state(NumberCapability::Lines)
+    );
+    assert_eq!(None, num(NumberCapability::Buttons));
+    assert!(db.number_caps().all(|(_, val)| val != Value::Number(-1)));
+    assert_eq!(Some(Value::Number(0xffff)), db.extended_cap("U8"));
+    assert_eq!(Some(Value::Number(0)), db.extended_cap("Xz"));
+    assert_eq!(State::Cancelled, db.extended_cap_state("Xn"));
+
+    // absent numbers of legacy entries are never returned
+    let db = TermInfo::from_path("tests/xterm").unwrap();
+    assert!(db.number_caps().all(|(_, val)| val != Value::Number(-1)));
+    assert!(db
+        .extended_caps()
+        .all(|(_, val)| !matches!(val, Value::Number(num) if num < 0)));
+    assert_eq!(None, db.number_cap(NumberCapability::Buttons));
+}
+
+#[test]
+fn borrowed() {
+    for path in fixtures() {
+        let data = fs::read(&path).unwrap();
+        let info = TermInfoRef::parse(&data).unwrap();
+        let owned = TermInfo::from_path(&path).unwrap();
+        assert_eq!(owned.name, info.name);
+        assert_eq!(owned.description, info.description);
+        assert!(owned.aliases.iter().eq(info.aliases()));
+        for &cap in BoolCapability::ALL {
+            assert_eq!(owned.flag_cap(cap), info.flag_cap(cap));
+        }
+        for &cap in NumberCapability::ALL {
+            assert_eq!(owned.number_cap(cap), info.number_cap(cap));
+        }
+        for &cap in StringCapability::ALL {
+            assert_eq!(owned.raw_string_cap(cap), info.raw_string_cap(cap));
+        }
+        // the borrowed capabilities are in the order they are stored in
+        let mut extended: Vec<_> = info.extended_caps().collect();
+        extended.sort_by_key(|&(name, _)| name);
+        assert!(owned.extended_caps().eq(extended));
+        for (name, val) in info.extended_caps() {
+            assert_eq!(Some(val), info.extended_cap(name));
+        }
+
+        let converted = TermInfo::from(info);
+        let options = DumpOptions::default();
+        assert_eq!(
+            owned.dump(options).to_string(),
+            converted.dump(options).to_string()
+        );
+    }
+
+    let data = fs::read("tests/xterm-256color").unwrap();
+    assert!(matches!(
+        TermInfoRef::parse(&data[..data.len() / 2]),
+        Err(Error::Io(_))
+    ));
+}
+
+#[test]
+fn parse_reader() {
+    let data = fs::read("tests/xterm-256color").unwrap();
+    let info = TermInfo::parse(&*data).unwrap();
+    assert_eq!("xterm-256color", info.name);
+    assert!(matches!(
+        TermInfo::parse(&data[..data.len() / 2]),
+        Err(Error::Io(err)) if err.kind() == std::io::ErrorKind::UnexpectedEof
+    ));
+    // readers are not read to the end if they exceed the maximum entry size
+    assert!(matches!(
+        TermInfo::parse(std::io::repeat(0)),
+        Err(Error::EntryTooLarge { size: 32769 })
+    ));
+    let mut padded = data.clone();
+    padded.resize(32768, 0);
+    assert_eq!("xterm-256color", TermInfo::parse(&*padded).unwrap().name);
+    padded.push(0);
+    assert!(matches!(
+        TermInfo::parse(&*padded),
+        Err(Error::EntryTooLarge { .. })
+    ));
+}
+
+#[test]
+fn search_path() {
+    let dir = TempDir::new("search");
+    let first = dir.join("first");
+    let second = dir.join("second");
+    TermInfo::from_path("tests/st-256color")
+        .unwrap()
+        .install(&second)
+        .unwrap();
+    let mut xterm = TermInfo::from_path("tests/xterm").unwrap();
+    xterm.aliases.push("st-256color".to_owned());
+    xterm.install_with_layout(&first, Layout::Hex).unwrap();
+
+    let mut search: SearchPath = ["missing".into(), second.clone()].into_iter().collect();
+    let db = TermInfo::from_name_in(&search, "stterm-256color").unwrap();
+    assert_eq!("st-256color", db.name);
+    assert_eq!(Some(&*second.join("s/stterm-256color")), db.path());
+    let Err(Error::NotFound(not_found)) = TermInfo::from_name_in(&search, "xterm") else {
+        panic!("xterm should not be found");
+    };
+    assert_eq!("xterm", not_found.name);
+    assert_eq!(search.dirs(), not_found.dirs);
+    // missing directories are skipped
+    let candidates = [second.join("x/xterm"), second.join("78/xterm")];
+    assert_eq!(candidates, &*not_found.candidates);
+    assert_eq!(
+        format!(
+            "Terminfo entry xterm not found, searched {}, {}",
+            candidates[0].display(),
+            candidates[1].display()
+        ),
+        not_found.to_string()
+    );
+
+    // earlier directories shadow later directories
+    search.prepend(&first);
+    assert_eq!(
+        "xterm",
+        TermInfo::from_name_in(&search, "st-256color").unwrap().name
+    );
+    assert_eq!(
+        first.join("73/st-256color"),
+        search.find("st-256color").unwrap()
+    );
+    assert!(search.remove(&first));
+    assert!(!search.remove(&first));
+    assert_eq!(&[PathBuf::from("missing"), second], search.dirs());
+    assert!(SearchPath::new().find("st-256color").is_err());
+    assert!(search.find("").is_err());
+    assert_eq!(None, TermInfo::from_source("test|test,").unwrap().path());
+}
+
+#[test]
+fn untrusted_names() {
+    for path in fixtures() {
+        assert!(is_valid_name(path.file_name().unwrap().to_str().unwrap()));
+    }
+    let long = "x".repeat(129);
+    for name in [
+        "",
+        "../../../tmp/evil",
+        "a/b",
+        "x\0",
+        ".hidden",
+        "-x",
+        "a b",
+        &long,
+    ] {
+        assert!(!is_valid_name(name), "{name}");
+        assert!(matches!(
+            TermInfo::from_name_in(&SearchPath::from_env(), name),
+            Err(Error::InvalidTermName(invalid)) if invalid == name
+        ));
+    }
+
+    // an empty TERM is treated like an unset TERM
+    let empty = TermInfo::from_term(Some(String::new()));
+    let unset = TermInfo::from_term(None);
+    for res in [empty, unset] {
+        assert!(matches!(res, Err(Error::NotFound(not_found)) if not_found.name.is_empty()));
+    }
+
+    let dir = TempDir::new("untrusted");
+    // directories are not entries
+    fs::create_dir_all(dir.join("x/xterm")).unwrap();
+    let search: SearchPath = [&*dir].into_iter().collect();
+    assert!(matches!(
+        TermInfo::from_name_in(&search, "xterm"),
+        Err(Error::NotFound(_))
+    ));
+    assert!(matches!(
+        TermInfo::from_path(dir.join("x/xterm")),
+        Err(Error::Io(_))
+    ));
+
+    // larger files are rejected without parsing them
+    let mut data = fs::read("tests/xterm").unwrap();
+    data.resize(32769, 0);
+    fs::create_dir_all(dir.join("b")).unwrap();
+    fs::write(dir.join("b/big"), &data).unwrap();
+    assert!(matches!(
+        TermInfo::from_name_in(&search, "big"),
+        Err(Error::EntryTooLarge { size: 32769 })
+    ));
+    data.truncate(32768);
+    fs::write(dir.join("b/big"), &data).unwrap();
+    assert_eq!(
+        "xterm",
+        TermInfo::from_name_in(&search, "big").unwrap().name
+    );
+}
+
+#[test]
+fn hashed_db() {
+    // generated from the entries in tests/ by tests/hashed/generate.py
+    for db in ["tests/hashed/terminfo.db", "tests/hashed/small-pages.db"] {
+        let search: SearchPath = [db].into_iter().collect();
+        let mut found = 0;
+        for path in fixtures() {
+            let expected = TermInfo::from_path(&path).unwrap();
+            // entries stored in multiple files are only stored once in the database
+            if path.file_name().unwrap() != &*expected.name
+                && Path::new("tests").join(&expected.name).is_file()
+            {
+                continue;
+            }
+            let info = match TermInfo::from_name_in(&search, &expected.name) {
+                Ok(info) => info,
+                Err(Error::NotFound(_)) => continue,
+                Err(err) => panic!("{}: {err}", expected.name),
+            };
+            found += 1;
+            assert_eq!(Some(Path::new(db)), info.path());
+            assert_eq!(expected.name, info.name);
+            assert_same_caps(&expected, &info);
+        }
+        assert!(found >= 7, "{db}");
+    }
+
+    let search: SearchPath = ["tests/hashed/small-pages.db"].into_iter().collect();
+    // an alias of st-256color
+    let info = TermInfo::from_name_in(&search, "stterm-256color").unwrap();
+    assert_eq!("st-256color", info.name);
+    let Err(Error::NotFound(not_found)) = TermInfo::from_name_in(&search, "kitty") else {
+        panic!("kitty is not part of the database");
+    };
+    assert_eq!(search.dirs(), not_found.candidates);
+
+    // corrupted databases are skipped
+    let dir = TempDir::new("hashed");
+    let corrupted = dir.join("terminfo.db");
+    let entries = dir.join("terminfo");
+    TermInfo::from_path("tests/xterm")
+        .unwrap()
+        .install(&entries)
+        .unwrap();
+    let search: SearchPath = [&corrupted, &entries].into_iter().collect();
+    let mut data = fs::read("tests/hashed/terminfo.db").unwrap();
+    data.truncate(data.len() / 2);
+    for data in [&data[..], &data[..100], b"\x61\x15\x06"] {
+        fs::write(&corrupted, data).unwrap();
+        let info = TermInfo::from_name_in(&search, "xterm").unwrap();
+        assert_eq!(Some(&*entries.join("x/xterm")), info.path());
+        let Err(Error::NotFound(not_found)) = TermInfo::from_name_in(&search, "kitty") else {
+            panic!("kitty should not be found");
+        };
+        assert_eq!([&*corrupted], &*not_found.skipped);
+        assert!(not_found.to_string().contains(&format!(
+            "; skipped invalid database {}",
+            corrupted.display()
+        )));
+        assert!(search.entries().iter().any(|entry| entry.name == "xterm"));
+    }
+
+    // cycles in the pages of a bucket are detected without trusting the page count
+    // of the header, all buckets are searched for databases with a custom hash
+    let mut data = fs::read("tests/hashed/small-pages.db").unwrap();
+    data[32..36].copy_from_slice(&u32::MAX.to_be_bytes());
+    data[92..96].copy_from_slice(&[0; 4]);
+    let first_bucket = u32::from_be_bytes(data[96..100].try_into().unwrap());
+    let next = 512 * first_bucket as usize + 16;
+    data[next..next + 4].copy_from_slice(&first_bucket.to_be_bytes());
+    fs::write(&corrupted, &data).unwrap();
+    let Err(Error::NotFound(not_found)) = TermInfo::from_name_in(&search, "kitty") else {
+        panic!("kitty should not be found");
+    };
+    assert_eq!([&*corrupted], &*not_found.skipped);
+    assert!(search.entries().iter().all(|entry| entry.path != corrupted));
+
+    // masks that don't map keys to an existing bucket
+    let mut data = fs::read("tests/hashed/terminfo.db").unwrap();
+    data[76..84].copy_from_slice(&[0xff; 8]);
+    fs::write(&corrupted, &data).unwrap();
+    let Err(Error::NotFound(not_found)) = TermInfo::from_name_in(&search, "linux") else {
+        panic!("linux should not be found");
+    };
+    assert_eq!([&*corrupted], &*not_found.skipped);
+}
+
+#[test]
+fn search_path_entries() {
+    let dir = TempDir::new("entries");
+    let first = dir.join("first");
+    let second = dir.join("second");
+    let st = TermInfo::from_path("tests/st-256color").unwrap();
+    st.install(&first).unwrap();
+    TermInfo::from_path("tests/xterm")
+        .unwrap()
+        .install_with_layout(&second, Layout::Hex)
+        .unwrap();
+    // the same name in both layouts of a directory
+    let mut linux = TermInfo::from_path("tests/linux").unwrap();
+    linux.install_with_layout(&second, Layout::Hex).unwrap();
+    linux.description = "first letter layout".to_owned();
+    linux.install(&second).unwrap();
+    fs::write(second.join("78/xterm-broken"), b"not an entry").unwrap();
+
+    let search: SearchPath = [
+        first.clone(),
+        second.clone(),
+        "tests/hashed/small-pages.db".into(),
+    ]
+    .into_iter()
+    .collect();
+    let entries = search.entries();
+    let names: Vec<_> = entries.iter().map(|entry| &*entry.name).collect();
+    assert_eq!(
+        [
+            "dumb",
+            "linux",
+            "screen",
+            "st-256color",
+            "tmux-256color",
+            "xterm",
+            "xterm-256color"
+        ],
+        &*names
+    );
+    for entry in &entries {
+        let info = TermInfo::from_name_in(&search, &entry.name).unwrap();
+        assert_eq!(Some(&*entry.path), info.path());
+        assert_eq!(info.description, entry.description);
+        assert_eq!(info.aliases, entry.aliases);
+    }
+    assert_eq!(first.join("s/st-256color"), entries[3].path);
+    assert_eq!(["stterm-256color"], &*entries[3].aliases);
+    assert_eq!("first letter layout", entries[1].description);
+    assert_eq!(second.join("78/xterm"), entries[5].path);
+    assert_eq!(Path::new("tests/hashed/small-pages.db"), entries[6].path);
+}
+
+#[test]
+fn not_found_suggestions() {
+    let search: SearchPath = ["tests/hashed/terminfo.db"].into_iter().collect();
+    for (name, expected) in [
+        ("xterm-256colour", "xterm-256color"),
+        ("alacrity", "alacritty"),
+        ("XTERM", "xterm"),
+        ("tmux256color", "tmux-256color"),
+    ] {
+        let Err(Error::NotFound(not_found)) = TermInfo::from_name_in(&search, name) else {
+            panic!("{name} should not be found");
+        };
+        assert_eq!(
+            Some(expected),
+            not_found.suggestions().first().map(|s| &**s)
+        );
+        assert!(not_found.suggestions().len() <= 5);
+    }
+    let Err(Error::NotFound(not_found)) = TermInfo::from_name_in(&search, "qqqqqqqq") else {
+        panic!("qqqqqqqq should not be found");
+    };
+    assert!(not_found.suggestions().is_empty());
+
+    // directories are searched as well
+    let dir = TempDir::new("suggest");
+    TermInfo::from_path("tests/xterm-256color")
+        .unwrap()
+        .install_with_layout(&dir, Layout::Hex)
+        .unwrap();
+    let search: SearchPath = [&*dir].into_iter().collect();
+    let Err(Error::NotFound(not_found)) = TermInfo::from_name_in(&search, "xterm-265color") else {
+        panic!("xterm-265color should not be found");
+    };
+    assert_eq!(["xterm-256color"], *not_found.suggestions());
+    // displaying the error doesn't search the directories again
+    assert!(!not_found.to_string().contains("xterm-256color"));
+}
+
+#[test]
+fn fallbacks() {
+    let fallbacks = Fallbacks::default();
+    assert_eq!(
+        ["xterm-kitty", "xterm-256color", "xterm", "ansi", "dumb"],
+        &*fallbacks.names("xterm-kitty")
+    );
+    assert_eq!(
+        [
+            "screen.xterm-256color",
+            "screen.xterm",
+            "screen",
+            "ansi",
+            "dumb"
+        ],
+        &*fallbacks.names("screen.xterm-256color")
+    );
+    assert_eq!(["ansi", "dumb"], &*fallbacks.names(""));
+
+    // contains xterm, xterm-256color, screen, tmux-256color and dumb but not ansi
+    let search: SearchPath = ["tests/hashed/small-pages.db"].into_iter().collect();
+    for (name, expected, fallback) in [
+        ("xterm-256color", "xterm-256color", None),
+        ("xterm-kitty", "xterm-256color", Some("xterm-256color")),
+        ("tmux-direct", "screen", Some("screen")),
+        ("xterm-foo-bar", "xterm", Some("xterm")),
+        ("unknown", "dumb", Some("dumb")),
+        ("", "dumb", Some("dumb")),
+        ("../../etc/passwd", "dumb", Some("dumb")),
+    ] {
+        let resolved = fallbacks.resolve(&search, name).unwrap();
+        assert_eq!(expected, resolved.info.name, "{name}");
+        assert_eq!(name, resolved.requested);
+        assert_eq!(fallback, resolved.fallback.as_deref(), "{name}");
+    }
+
+    let mut fallbacks = Fallbacks::new();
+    fallbacks.add_name("a", "b");
+    fallbacks.add_name("b", "a");
+    assert_eq!(["a", "b"], &*fallbacks.names("a"));
+    fallbacks.strip_suffixes(false);
+    assert_eq!(["xterm-kitty"], &*fallbacks.names("xterm-kitty"));
+    let Err(Error::NotFound(not_found)) = fallbacks.resolve(&search, "xterm-256colour") else {
+        panic!("xterm-256colour should not be found");
+    };
+    assert_eq!("xterm-256colour", not_found.name);
+    assert_eq!(
+        Some("xterm-256color"),
+        not_found.suggestions().first().map(|s| &**s)
+    );
+    assert!(matches!(
+        fallbacks.resolve(&search, "a b"),
+        Err(Error::InvalidTermName(_))
+    ));
+}
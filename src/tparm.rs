@@ -0,0 +1,878 @@
+//! Expansion of parameterized strings as described in the
+//! [`terminfo(5)`](https://manpages.debian.org/bookworm/ncurses-bin/terminfo.5.en.html#Parameterized_Strings)
+//! manpage.
+
+use std::{fmt, io};
+
+use crate::Error;
+
+/// maximum depth of the evaluation stack (same as ncurses)
+const STACK_SIZE: usize = 20;
+/// maximum number of parameters a capability can reference (`%p1`..`%p9`)
+const MAX_PARAMS: usize = 9;
+
+/// A parameter passed to a parameterized string capability
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Param<'a> {
+    Number(i32),
+    String(&'a [u8]),
+}
+
+impl Default for Param<'_> {
+    fn default() -> Self {
+        Param::Number(0)
+    }
+}
+
+impl From<i32> for Param<'_> {
+    fn from(val: i32) -> Self {
+        Param::Number(val)
+    }
+}
+
+impl<'a> From<&'a [u8]> for Param<'a> {
+    fn from(val: &'a [u8]) -> Self {
+        Param::String(val)
+    }
+}
+
+impl<'a> From<&'a str> for Param<'a> {
+    fn from(val: &'a str) -> Self {
+        Param::String(val.as_bytes())
+    }
+}
+
+/// The static variables (`%PA`..`%PZ`) of parameterized strings.
+///
+/// Static variables retain their value between expansions, so the same
+/// `Variables` should be passed to every expansion for a terminal.
+/// Dynamic variables (`%Pa`..`%Pz`) are reset for each expansion.
+#[derive(Debug, Default, PartialEq, Eq, Clone)]
+pub struct Variables {
+    static_vars: [i32; 26],
+}
+
+impl Variables {
+    pub fn new() -> Variables {
+        Variables::default()
+    }
+}
+
+/// Expands the parameterized string `template` with `params`
+///
+/// # Example
+/// ```
+/// use termini::{expand, Variables};
+///
+/// let mut vars = Variables::new();
+/// let res = expand(b"\x1b[%i%p1%d;%p2%dH", &[4.into(), 9.into()], &mut vars).unwrap();
+/// assert_eq!(res, b"\x1b[5;10H");
+/// ```
+pub fn expand(template: &[u8], params: &[Param], vars: &mut Variables) -> Result<Vec<u8>, Error> {
+    let mut res = Vec::with_capacity(template.len());
+    expand_into(template, params, vars, &mut IoOutput(&mut res))?;
+    Ok(res)
+}
+
+/// Expands the parameterized string `template` with `params` directly into `out`
+/// without allocating.
+///
+/// The expansion is written in many small pieces, so `out` should be buffered.
+///
+/// # Example
+/// ```
+/// use termini::{expand_to, Variables};
+///
+/// let mut vars = Variables::new();
+/// let mut out = Vec::new();
+/// expand_to(b"\x1b[%p1%dm", &[31.into()], &mut vars, &mut out).unwrap();
+/// assert_eq!(out, b"\x1b[31m");
+/// ```
+pub fn expand_to(
+    template: &[u8],
+    params: &[Param],
+    vars: &mut Variables,
+    mut out: impl io::Write,
+) -> Result<(), Error> {
+    expand_into(template, params, vars, &mut IoOutput(&mut out))
+}
+
+/// Expands the parameterized string `template` with `params` directly into `out`
+/// without allocating.
+///
+/// Returns [`Error::Fmt`] if writing fails or the expansion is not valid UTF-8.
+///
+/// # Example
+/// ```
+/// use termini::{expand_to_fmt, Variables};
+///
+/// let mut vars = Variables::new();
+/// let mut out = String::new();
+/// expand_to_fmt(b"\x1b[%p1%dm", &[31.into()], &mut vars, &mut out).unwrap();
+/// assert_eq!(out, "\x1b[31m");
+/// ```
+pub fn expand_to_fmt(
+    template: &[u8],
+    params: &[Param],
+    vars: &mut Variables,
+    mut out: impl fmt::Write,
+) -> Result<(), Error> {
+    expand_into(template, params, vars, &mut FmtOutput(&mut out))
+}
+
+/// A destination for expanded strings
+pub(crate) trait Output {
+    fn write_bytes(&mut self, bytes: &[u8]) -> Result<(), Error>;
+
+    fn write_repeated(&mut self, byte: u8, mut count: usize) -> Result<(), Error> {
+        let buf = [byte; 16];
+        while count != 0 {
+            let len = count.min(buf.len());
+            self.write_bytes(&buf[..len])?;
+            count -= len;
+        }
+        Ok(())
+    }
+}
+
+pub(crate) struct IoOutput<W>(pub(crate) W);
+
+impl<W: io::Write> Output for IoOutput<W> {
+    fn write_bytes(&mut self, bytes: &[u8]) -> Result<(), Error> {
+        self.0.write_all(bytes)?;
+        Ok(())
+    }
+}
+
+pub(crate) struct FmtOutput<W>(pub(crate) W);
+
+impl<W: fmt::Write> Output for FmtOutput<W> {
+    fn write_bytes(&mut self, bytes: &[u8]) -> Result<(), Error> {
+        // literals are never split inside a UTF-8 sequence because `%` is ASCII
+        let str = std::str::from_utf8(bytes).map_err(|_| fmt::Error)?;
+        self.0.write_str(str)?;
+        Ok(())
+    }
+}
+
+pub(crate) fn expand_into(
+    template: &[u8],
+    params: &[Param],
+    vars: &mut Variables,
+    out: &mut impl Output,
+) -> Result<(), Error> {
+    let mut machine = Machine::new(params, !references_params(template));
+    let mut ops = Ops::new(template);
+    while let Some(op) = ops.next_op()? {
+        match op {
+            Op::Then => {
+                if !machine.condition()? {
+                    ops.skip_branch(true)?;
+                }
+            }
+            Op::Else => ops.skip_branch(false)?,
+            op => machine.exec(op, template, vars, out)?,
+        }
+    }
+    Ok(())
+}
+
+/// A parameterized string compiled for repeated expansion.
+///
+/// Compiling validates the whole template up front (including balanced conditionals),
+/// so malformed templates are reported by [`Program::compile`] instead of during expansion.
+///
+/// # Example
+/// ```
+/// use termini::{Program, Variables};
+///
+/// let program = Program::compile(b"\x1b[%i%p1%d;%p2%dH").unwrap();
+/// let mut vars = Variables::new();
+/// let mut out = Vec::new();
+/// for col in 0..3 {
+///     program.expand_to(&[0.into(), col.into()], &mut vars, &mut out).unwrap();
+/// }
+/// assert_eq!(out, b"\x1b[1;1H\x1b[1;2H\x1b[1;3H");
+/// ```
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Program {
+    template: Box<[u8]>,
+    instructions: Box<[Instruction]>,
+    termcap_style: bool,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+enum Instruction {
+    Op(Op),
+    /// `%t`, jumps to the target if the popped condition is false
+    JumpUnless(usize),
+    /// `%e`, jumps to the end of the conditional
+    Jump(usize),
+}
+
+/// an open `%?` during compilation
+struct Conditional {
+    /// offset of the `%?`
+    start: usize,
+    /// the last `%t` whose target is the next `%e` or `%;`
+    then: Option<usize>,
+    /// the `%e` jumps whose target is the `%;`
+    ends: Vec<usize>,
+}
+
+impl Program {
+    /// Compiles the parameterized string `template`
+    pub fn compile(template: &[u8]) -> Result<Program, Error> {
+        let mut instructions = Vec::new();
+        let mut conditionals: Vec<Conditional> = Vec::new();
+        let mut ops = Ops::new(template);
+        let mut termcap_style = true;
+        loop {
+            let start = ops.pos();
+            let Some(op) = ops.next_op()? else {
+                break;
+            };
+            let invalid = || Error::InvalidTemplate { offset: start };
+            match op {
+                Op::If => conditionals.push(Conditional {
+                    start,
+                    then: None,
+                    ends: Vec::new(),
+                }),
+                Op::Then => {
+                    let cond = conditionals.last_mut().ok_or_else(invalid)?;
+                    if cond.then.is_some() {
+                        return Err(invalid());
+                    }
+                    cond.then = Some(instructions.len());
+                    instructions.push(Instruction::JumpUnless(0));
+                }
+                Op::Else => {
+                    let cond = conditionals.last_mut().ok_or_else(invalid)?;
+                    let then = cond.then.take().ok_or_else(invalid)?;
+                    cond.ends.push(instructions.len());
+                    instructions.push(Instruction::Jump(0));
+                    instructions[then] = Instruction::JumpUnless(instructions.len());
+                }
+                Op::EndIf => {
+                    let cond = conditionals.pop().ok_or_else(invalid)?;
+                    let end = instructions.len();
+                    if let Some(then) = cond.then {
+                        instructions[then] = Instruction::JumpUnless(end);
+                    }
+                    for jump in cond.ends {
+                        instructions[jump] = Instruction::Jump(end);
+                    }
+                }
+                op => {
+                    if matches!(op, Op::PushParam(_)) {
+                        termcap_style = false;
+                    }
+                    instructions.push(Instruction::Op(op))
+                }
+            }
+        }
+        if let Some(cond) = conditionals.pop() {
+            return Err(Error::InvalidTemplate { offset: cond.start });
+        }
+        Ok(Program {
+            template: template.into(),
+            instructions: instructions.into_boxed_slice(),
+            termcap_style,
+        })
+    }
+
+    /// Expands the program with `params`
+    pub fn expand(&self, params: &[Param], vars: &mut Variables) -> Result<Vec<u8>, Error> {
+        let mut res = Vec::with_capacity(self.template.len());
+        self.run(params, vars, &mut IoOutput(&mut res))?;
+        Ok(res)
+    }
+
+    /// Expands the program with `params` directly into `out` without allocating.
+    pub fn expand_to(
+        &self,
+        params: &[Param],
+        vars: &mut Variables,
+        mut out: impl io::Write,
+    ) -> Result<(), Error> {
+        self.run(params, vars, &mut IoOutput(&mut out))
+    }
+
+    /// Expands the program with `params` directly into `out` without allocating.
+    ///
+    /// Returns [`Error::Fmt`] if writing fails or the expansion is not valid UTF-8.
+    pub fn expand_to_fmt(
+        &self,
+        params: &[Param],
+        vars: &mut Variables,
+        mut out: impl fmt::Write,
+    ) -> Result<(), Error> {
+        self.run(params, vars, &mut FmtOutput(&mut out))
+    }
+
+    fn run(
+        &self,
+        params: &[Param],
+        vars: &mut Variables,
+        out: &mut impl Output,
+    ) -> Result<(), Error> {
+        let mut machine = Machine::new(params, self.termcap_style);
+        let mut pc = 0;
+        while let Some(&instruction) = self.instructions.get(pc) {
+            pc += 1;
+            match instruction {
+                Instruction::Op(op) => machine.exec(op, &self.template, vars, out)?,
+                Instruction::JumpUnless(target) => {
+                    if !machine.condition()? {
+                        pc = target
+                    }
+                }
+                Instruction::Jump(target) => pc = target,
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Returns whether `template` contains any `%p`.
+/// Termcap-style strings without `%p` pop their parameters directly from the stack.
+pub(crate) fn references_params(template: &[u8]) -> bool {
+    let mut ops = Ops::new(template);
+    while let Ok(Some(op)) = ops.next_op() {
+        if matches!(op, Op::PushParam(_)) {
+            return true;
+        }
+    }
+    false
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub(crate) enum Op {
+    /// bytes of the template that are copied to the output verbatim
+    Literal {
+        start: usize,
+        end: usize,
+    },
+    /// `%c`
+    Char,
+    /// `%d`, `%o`, `%x`, `%X`, `%s` with optional printf-style flags
+    Format(Format),
+    /// `%p[1-9]`, the index is zero based
+    PushParam(u8),
+    /// `%P[a-z]`, `%P[A-Z]`
+    SetVar(Var),
+    /// `%g[a-z]`, `%g[A-Z]`
+    GetVar(Var),
+    /// `%'c'` and `%{nn}`
+    PushInt(i32),
+    /// `%l`
+    StrLen,
+    /// `%i`
+    Increment,
+    Binary(BinOp),
+    /// `%!`
+    Not,
+    /// `%~`
+    Complement,
+    /// `%?`
+    If,
+    /// `%t`
+    Then,
+    /// `%e`
+    Else,
+    /// `%;`
+    EndIf,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub(crate) enum Var {
+    Static(u8),
+    Dynamic(u8),
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub(crate) enum BinOp {
+    Add,
+    Sub,
+    Mul,
+    Div,
+    Mod,
+    BitAnd,
+    BitOr,
+    BitXor,
+    Eq,
+    Lt,
+    Gt,
+    And,
+    Or,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub(crate) enum Conversion {
+    Decimal,
+    Octal,
+    LowerHex,
+    UpperHex,
+    String,
+}
+
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub(crate) struct Format {
+    conversion: Conversion,
+    left_align: bool,
+    sign: bool,
+    space: bool,
+    alternate: bool,
+    zero_pad: bool,
+    width: usize,
+    precision: Option<usize>,
+}
+
+/// tokenizer for parameterized strings
+pub(crate) struct Ops<'a> {
+    src: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Ops<'a> {
+    pub(crate) fn new(src: &'a [u8]) -> Ops<'a> {
+        Ops { src, pos: 0 }
+    }
+
+    /// offset of the next op in the template
+    pub(crate) fn pos(&self) -> usize {
+        self.pos
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.src.get(self.pos).copied()
+    }
+
+    fn bump(&mut self) -> Result<u8, Error> {
+        let c = self
+            .peek()
+            .ok_or(Error::InvalidTemplate { offset: self.pos })?;
+        self.pos += 1;
+        Ok(c)
+    }
+
+    fn number(&mut self) -> usize {
+        let mut res = 0usize;
+        while let Some(c @ b'0'..=b'9') = self.peek() {
+            res = res.saturating_mul(10).saturating_add((c - b'0') as usize);
+            self.pos += 1;
+        }
+        res
+    }
+
+    pub(crate) fn next_op(&mut self) -> Result<Option<Op>, Error> {
+        let start = self.pos;
+        let Some(c) = self.peek() else {
+            return Ok(None);
+        };
+        if c != b'%' {
+            let len = self.src[start..]
+                .iter()
+                .position(|&c| c == b'%')
+                .unwrap_or(self.src.len() - start);
+            self.pos += len;
+            return Ok(Some(Op::Literal {
+                start,
+                end: self.pos,
+            }));
+        }
+        self.pos += 1;
+        let invalid = Error::InvalidTemplate { offset: start };
+        let op = match self.bump()? {
+            b'%' => Op::Literal {
+                start: start + 1,
+                end: start + 2,
+            },
+            b'c' => Op::Char,
+            b'p' => match self.bump()? {
+                c @ b'1'..=b'9' => Op::PushParam(c - b'1'),
+                _ => return Err(invalid),
+            },
+            b'P' => Op::SetVar(self.var().ok_or(invalid)?),
+            b'g' => Op::GetVar(self.var().ok_or(invalid)?),
+            b'\'' => {
+                let c = self.bump()?;
+                if self.bump()? != b'\'' {
+                    return Err(invalid);
+                }
+                Op::PushInt(c.into())
+            }
+            b'{' => {
+                let digits = self.pos;
+                let val = self.number().min(i32::MAX as usize) as i32;
+                if digits == self.pos || self.bump()? != b'}' {
+                    return Err(invalid);
+                }
+                Op::PushInt(val)
+            }
+            b'l' => Op::StrLen,
+            b'i' => Op::Increment,
+            b'+' => Op::Binary(BinOp::Add),
+            b'-' => Op::Binary(BinOp::Sub),
+            b'*' => Op::Binary(BinOp::Mul),
+            b'/' => Op::Binary(BinOp::Div),
+            b'm' => Op::Binary(BinOp::Mod),
+            b'&' => Op::Binary(BinOp::BitAnd),
+            b'|' => Op::Binary(BinOp::BitOr),
+            b'^' => Op::Binary(BinOp::BitXor),
+            b'=' => Op::Binary(BinOp::Eq),
+            b'<' => Op::Binary(BinOp::Lt),
+            b'>' => Op::Binary(BinOp::Gt),
+            b'A' => Op::Binary(BinOp::And),
+            b'O' => Op::Binary(BinOp::Or),
+            b'!' => Op::Not,
+            b'~' => Op::Complement,
+            b'?' => Op::If,
+            b't' => Op::Then,
+            b'e' => Op::Else,
+            b';' => Op::EndIf,
+            _ => {
+                self.pos = start + 1;
+                Op::Format(self.format().ok_or(invalid)?)
+            }
+        };
+        Ok(Some(op))
+    }
+
+    fn var(&mut self) -> Option<Var> {
+        let var = match self.peek()? {
+            c @ b'a'..=b'z' => Var::Dynamic(c - b'a'),
+            c @ b'A'..=b'Z' => Var::Static(c - b'A'),
+            _ => return None,
+        };
+        self.pos += 1;
+        Some(var)
+    }
+
+    /// `%[[:]flags][width[.precision]][doxXs]`
+    fn format(&mut self) -> Option<Format> {
+        let mut format = Format {
+            conversion: Conversion::Decimal,
+            left_align: false,
+            sign: false,
+            space: false,
+            alternate: false,
+            zero_pad: false,
+            width: 0,
+            precision: None,
+        };
+        // `-` and `+` are only flags after `:`, otherwise they are operators
+        let colon = self.peek() == Some(b':');
+        if colon {
+            self.pos += 1;
+        }
+        loop {
+            match self.peek()? {
+                b'-' if colon => format.left_align = true,
+                b'+' if colon => format.sign = true,
+                b' ' => format.space = true,
+                b'#' => format.alternate = true,
+                _ => break,
+            }
+            self.pos += 1;
+        }
+        if self.peek() == Some(b'0') {
+            format.zero_pad = true;
+        }
+        format.width = self.number();
+        if self.peek() == Some(b'.') {
+            self.pos += 1;
+            format.precision = Some(self.number());
+        }
+        format.conversion = match self.peek()? {
+            b'd' => Conversion::Decimal,
+            b'o' => Conversion::Octal,
+            b'x' => Conversion::LowerHex,
+            b'X' => Conversion::UpperHex,
+            b's' => Conversion::String,
+            _ => return None,
+        };
+        self.pos += 1;
+        Some(format)
+    }
+
+    /// Skips the remainder of a conditional branch.
+    /// If `to_else` is true the skip stops after the next `%e` (or `%;`)
+    /// at the same nesting level, otherwise only `%;` ends the branch.
+    fn skip_branch(&mut self, to_else: bool) -> Result<(), Error> {
+        let mut depth = 0u32;
+        while let Some(op) = self.next_op()? {
+            match op {
+                Op::If => depth += 1,
+                Op::EndIf if depth == 0 => break,
+                Op::EndIf => depth -= 1,
+                Op::Else if depth == 0 && to_else => break,
+                _ => (),
+            }
+        }
+        Ok(())
+    }
+}
+
+struct Stack<'a> {
+    items: [Param<'a>; STACK_SIZE],
+    len: usize,
+}
+
+impl<'a> Stack<'a> {
+    fn push(&mut self, val: Param<'a>) -> Result<(), Error> {
+        let slot = self.items.get_mut(self.len).ok_or(Error::StackOverflow)?;
+        *slot = val;
+        self.len += 1;
+        Ok(())
+    }
+
+    fn pop(&mut self) -> Result<Param<'a>, Error> {
+        if self.len == 0 {
+            return Err(Error::StackUnderflow);
+        }
+        self.len -= 1;
+        Ok(self.items[self.len])
+    }
+
+    fn pop_number(&mut self) -> Result<i32, Error> {
+        match self.pop()? {
+            Param::Number(val) => Ok(val),
+            Param::String(_) => Err(Error::TypeMismatch),
+        }
+    }
+
+    fn pop_string(&mut self) -> Result<&'a [u8], Error> {
+        match self.pop()? {
+            Param::String(val) => Ok(val),
+            Param::Number(_) => Err(Error::TypeMismatch),
+        }
+    }
+}
+
+/// evaluation state of a single expansion
+pub(crate) struct Machine<'a> {
+    params: [Param<'a>; MAX_PARAMS],
+    dynamic_vars: [i32; 26],
+    stack: Stack<'a>,
+    /// number of parameters preloaded onto the stack for termcap-style strings
+    preloaded: usize,
+    incremented: bool,
+}
+
+impl<'a> Machine<'a> {
+    /// If `termcap_style` is set the parameters are pushed onto the stack
+    /// so that successive pops yield `params[0]`, `params[1]`, ...
+    pub(crate) fn new(params: &[Param<'a>], termcap_style: bool) -> Machine<'a> {
+        let mut machine = Machine {
+            params: [Param::Number(0); MAX_PARAMS],
+            dynamic_vars: [0; 26],
+            stack: Stack {
+                items: [Param::Number(0); STACK_SIZE],
+                len: 0,
+            },
+            preloaded: 0,
+            incremented: false,
+        };
+        let len = params.len().min(MAX_PARAMS);
+        machine.params[..len].copy_from_slice(&params[..len]);
+        if termcap_style {
+            for (dst, &param) in machine
+                .stack
+                .items
+                .iter_mut()
+                .zip(params[..len].iter().rev())
+            {
+                *dst = param;
+            }
+            machine.stack.len = len;
+            machine.preloaded = len;
+        }
+        machine
+    }
+
+    /// pops the condition of a `%t`
+    pub(crate) fn condition(&mut self) -> Result<bool, Error> {
+        Ok(self.stack.pop_number()? != 0)
+    }
+
+    /// Executes everything except the control flow ops `%t` and `%e`
+    /// which must be handled by the caller.
+    pub(crate) fn exec(
+        &mut self,
+        op: Op,
+        template: &[u8],
+        vars: &mut Variables,
+        out: &mut impl Output,
+    ) -> Result<(), Error> {
+        let stack = &mut self.stack;
+        match op {
+            Op::Literal { start, end } => out.write_bytes(&template[start..end])?,
+            Op::Char => {
+                // like ncurses avoid emitting NUL which would terminate the string
+                let c = match stack.pop_number()? as u8 {
+                    0 => 0o200,
+                    c => c,
+                };
+                out.write_bytes(&[c])?
+            }
+            Op::Format(format) => match stack.pop()? {
+                Param::Number(val) if format.conversion != Conversion::String => {
+                    write_number(out, &format, val)?
+                }
+                Param::String(val) if format.conversion == Conversion::String => {
+                    write_string(out, &format, val)?
+                }
+                _ => return Err(Error::TypeMismatch),
+            },
+            Op::PushParam(i) => stack.push(self.params[i as usize])?,
+            Op::SetVar(Var::Static(i)) => vars.static_vars[i as usize] = stack.pop_number()?,
+            Op::SetVar(Var::Dynamic(i)) => self.dynamic_vars[i as usize] = stack.pop_number()?,
+            Op::GetVar(Var::Static(i)) => {
+                stack.push(Param::Number(vars.static_vars[i as usize]))?
+            }
+            Op::GetVar(Var::Dynamic(i)) => {
+                stack.push(Param::Number(self.dynamic_vars[i as usize]))?
+            }
+            Op::PushInt(val) => stack.push(Param::Number(val))?,
+            Op::StrLen => {
+                let len = stack.pop_string()?.len();
+                stack.push(Param::Number(len.try_into().unwrap_or(i32::MAX)))?
+            }
+            // like ncurses only the first `%i` has an effect
+            Op::Increment if !self.incremented => {
+                self.incremented = true;
+                for i in 0..2 {
+                    if let Param::Number(val) = &mut self.params[i] {
+                        *val = val.wrapping_add(1);
+                    }
+                    // termcap-style strings already pushed the parameters
+                    if i < self.preloaded && self.preloaded - 1 - i < stack.len {
+                        if let Param::Number(val) = &mut stack.items[self.preloaded - 1 - i] {
+                            *val = val.wrapping_add(1);
+                        }
+                    }
+                }
+            }
+            Op::Increment => (),
+            Op::Binary(op) => {
+                let rhs = stack.pop_number()?;
+                let lhs = stack.pop_number()?;
+                let res = match op {
+                    BinOp::Add => lhs.wrapping_add(rhs),
+                    BinOp::Sub => lhs.wrapping_sub(rhs),
+                    BinOp::Mul => lhs.wrapping_mul(rhs),
+                    // ncurses treats division by zero as zero
+                    BinOp::Div => lhs.checked_div(rhs).unwrap_or(0),
+                    BinOp::Mod => lhs.checked_rem(rhs).unwrap_or(0),
+                    BinOp::BitAnd => lhs & rhs,
+                    BinOp::BitOr => lhs | rhs,
+                    BinOp::BitXor => lhs ^ rhs,
+                    BinOp::Eq => (lhs == rhs).into(),
+                    BinOp::Lt => (lhs < rhs).into(),
+                    BinOp::Gt => (lhs > rhs).into(),
+                    BinOp::And => (lhs != 0 && rhs != 0).into(),
+                    BinOp::Or => (lhs != 0 || rhs != 0).into(),
+                };
+                stack.push(Param::Number(res))?
+            }
+            Op::Not => {
+                let val = stack.pop_number()?;
+                stack.push(Param::Number((val == 0).into()))?
+            }
+            Op::Complement => {
+                let val = stack.pop_number()?;
+                stack.push(Param::Number(!val))?
+            }
+            Op::If | Op::EndIf => (),
+            Op::Then | Op::Else => unreachable!("control flow is handled by the caller"),
+        }
+        Ok(())
+    }
+}
+
+fn write_padded(
+    out: &mut impl Output,
+    format: &Format,
+    prefix: &[u8],
+    body: &[u8],
+) -> Result<(), Error> {
+    let len = prefix.len() + body.len();
+    let padding = format.width.saturating_sub(len);
+    if format.left_align {
+        out.write_bytes(prefix)?;
+        out.write_bytes(body)?;
+        out.write_repeated(b' ', padding)
+    } else if format.zero_pad && format.precision.is_none() {
+        out.write_bytes(prefix)?;
+        out.write_repeated(b'0', padding)?;
+        out.write_bytes(body)
+    } else {
+        out.write_repeated(b' ', padding)?;
+        out.write_bytes(prefix)?;
+        out.write_bytes(body)
+    }
+}
+
+fn write_string(out: &mut impl Output, format: &Format, val: &[u8]) -> Result<(), Error> {
+    let len = format.precision.map_or(val.len(), |max| max.min(val.len()));
+    write_padded(out, format, &[], &val[..len])
+}
+
+fn write_number(out: &mut impl Output, format: &Format, val: i32) -> Result<(), Error> {
+    // sign/prefix and digits (u32 in octal needs 11 digits)
+    let mut prefix = [0u8; 2];
+    let mut prefix_len = 0;
+    let (mut num, radix) = match format.conversion {
+        Conversion::Decimal => {
+            let sign = if val < 0 {
+                Some(b'-')
+            } else if format.sign {
+                Some(b'+')
+            } else if format.space {
+                Some(b' ')
+            } else {
+                None
+            };
+            if let Some(sign) = sign {
+                prefix[0] = sign;
+                prefix_len = 1;
+            }
+            (val.unsigned_abs(), 10)
+        }
+        Conversion::Octal => (val as u32, 8),
+        Conversion::LowerHex | Conversion::UpperHex => {
+            if format.alternate && val != 0 {
+                let x = if format.conversion == Conversion::LowerHex {
+                    b'x'
+                } else {
+                    b'X'
+                };
+                prefix = [b'0', x];
+                prefix_len = 2;
+            }
+            (val as u32, 16)
+        }
+        Conversion::String => unreachable!(),
+    };
+
+    let digits: &[u8] = if format.conversion == Conversion::UpperHex {
+        b"0123456789ABCDEF"
+    } else {
+        b"0123456789abcdef"
+    };
+    let mut buf = [b'0'; 32];
+    let mut start = buf.len();
+    while num != 0 {
+        start -= 1;
+        buf[start] = digits[(num % radix) as usize];
+        num /= radix;
+    }
+    let min_digits = format.precision.unwrap_or(1).min(buf.len());
+    start = start.min(buf.len() - min_digits);
+    if format.conversion == Conversion::Octal && format.alternate && buf.get(start) != Some(&b'0') {
+        start -= 1;
+    }
+    write_padded(out, format, &prefix[..prefix_len], &buf[start..])
+}
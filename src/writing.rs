@@ -0,0 +1,202 @@
+//! Serialization of [`TermInfo`] into the compiled format described in term(5)
+
+use std::io;
+
+use crate::parsing::{MAGIC_32BIT, MAGIC_LEGACY};
+use crate::source::CANCELLED_STRING;
+use crate::{get_str_with_offset, Error, TermInfo, ValueStorage};
+
+/// Encoding of numeric capabilities in compiled terminfo files
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum Format {
+    /// 16-bit numbers (magic number `0432`), understood by all terminfo implementations.
+    /// Numbers larger than `i16::MAX` are clamped like `tic` does.
+    Legacy,
+    /// 32-bit numbers (magic number `01036`), introduced in ncurses 6.1
+    Number32,
+}
+
+impl TermInfo {
+    /// Writes the entry in the compiled format read by [`TermInfo::parse`].
+    /// Like `tic`, the legacy format is used unless a number doesn't fit into 16 bits.
+    ///
+    /// # Example
+    /// ```
+    /// use termini::TermInfo;
+    ///
+    /// if let Ok(info) = TermInfo::from_env() {
+    ///     let mut compiled = Vec::new();
+    ///     info.write_to(&mut compiled).unwrap();
+    ///     assert_eq!(TermInfo::parse(&*compiled).unwrap().name, info.name);
+    /// }
+    /// ```
+    pub fn write_to(&self, out: impl io::Write) -> Result<(), Error> {
+        let large = |num: &i32| i16::try_from(*num).is_err();
+        let ext_large = self
+            .extended
+            .capabilities
+            .values()
+            .any(|val| matches!(val, ValueStorage::Number(num) if large(num)));
+        let format = if self.data.numbers.iter().any(large) || ext_large {
+            Format::Number32
+        } else {
+            Format::Legacy
+        };
+        self.write_to_format(out, format)
+    }
+
+    /// Returns the names separated by `|`: the name, the aliases and the description.
+    /// The description is written even if it's empty when there are aliases,
+    /// otherwise the last alias would be read as the description.
+    pub(crate) fn names_section(&self) -> String {
+        let mut res = self.name.clone();
+        let aliases = self.aliases.iter().filter(|alias| !alias.is_empty());
+        for alias in aliases.clone() {
+            res.push('|');
+            res.push_str(alias);
+        }
+        if aliases.count() != 0 || !self.description.is_empty() {
+            res.push('|');
+            res.push_str(&self.description);
+        }
+        res
+    }
+
+    /// Writes the entry in the compiled format read by [`TermInfo::parse`] using the
+    /// number encoding `format`.
+    /// Returns [`Error::TableTooLarge`] if the entry exceeds the limits of the format.
+    pub fn write_to_format(&self, mut out: impl io::Write, format: Format) -> Result<(), Error> {
+        if self.name.is_empty() {
+            return Err(Error::NoNames);
+        }
+        let mut buf = Vec::new();
+
+        let names = self.names_section();
+        let data = &self.data;
+        let magic = match format {
+            Format::Legacy => MAGIC_LEGACY,
+            Format::Number32 => MAGIC_32BIT,
+        };
+        write_i16(&mut buf, magic);
+        write_len(&mut buf, names.len() + 1)?;
+        write_len(&mut buf, data.bools.len())?;
+        write_len(&mut buf, data.numbers.len())?;
+        write_len(&mut buf, data.strings.len())?;
+        write_len(&mut buf, data.str_table.len())?;
+
+        buf.extend_from_slice(names.as_bytes());
+        buf.push(b'\0');
+        buf.extend(data.bools.iter().map(|&val| u8::from(val)));
+        if buf.len() % 2 == 1 {
+            buf.push(b'\0');
+        }
+        for &num in &*data.numbers {
+            write_number(&mut buf, num, format);
+        }
+        for &off in &*data.strings {
+            buf.extend_from_slice(&off.to_le_bytes());
+        }
+        buf.extend_from_slice(&data.str_table);
+
+        self.write_extended(&mut buf, format)?;
+        out.write_all(&buf)?;
+        Ok(())
+    }
+
+    /// Appends the extended capabilities (if any) to `buf`. Each type is sorted by name
+    /// like in the output of `tic`.
+    fn write_extended(&self, buf: &mut Vec<u8>, format: Format) -> Result<(), Error> {
+        let mut bools = Vec::new();
+        let mut numbers = Vec::new();
+        let mut strings = Vec::new();
+        for (name, &val) in &self.extended.capabilities {
+            match val {
+                ValueStorage::True => bools.push(name.as_str()),
+                ValueStorage::Number(num) => numbers.push((name.as_str(), num)),
+                ValueStorage::String(off) => {
+                    if let Some(val) = get_str_with_offset(&self.extended.table, off, 0) {
+                        strings.push((name.as_str(), Some(val)))
+                    }
+                }
+                // cancelled capabilities of all types are stored as strings
+                ValueStorage::Cancelled => strings.push((name.as_str(), None)),
+            }
+        }
+        if bools.is_empty() && numbers.is_empty() && strings.is_empty() {
+            return Ok(());
+        }
+        if buf.len() % 2 == 1 {
+            buf.push(b'\0');
+        }
+
+        let mut offsets = Vec::new();
+        let mut table = Vec::new();
+        for &(_, val) in &strings {
+            let Some(val) = val else {
+                offsets.push(CANCELLED_STRING);
+                continue;
+            };
+            offsets.push(table_offset(table.len())?);
+            table.extend_from_slice(val);
+            table.push(b'\0');
+        }
+
+        let names_start = table.len();
+        let names = bools
+            .iter()
+            .copied()
+            .chain(numbers.iter().map(|&(name, _)| name))
+            .chain(strings.iter().map(|&(name, _)| name));
+        for name in names {
+            offsets.push(table_offset(table.len() - names_start)?);
+            table.extend_from_slice(name.as_bytes());
+            table.push(b'\0');
+        }
+
+        write_len(buf, bools.len())?;
+        write_len(buf, numbers.len())?;
+        write_len(buf, strings.len())?;
+        // cancelled strings are not counted as they aren't stored in the table
+        let cancelled = strings.iter().filter(|(_, val)| val.is_none()).count();
+        write_len(buf, offsets.len() - cancelled)?;
+        write_len(buf, table.len())?;
+
+        buf.extend(bools.iter().map(|_| 1));
+        if bools.len() % 2 == 1 {
+            buf.push(b'\0');
+        }
+        for &(_, num) in &numbers {
+            write_number(buf, num, format);
+        }
+        for off in offsets {
+            buf.extend_from_slice(&off.to_le_bytes());
+        }
+        buf.extend_from_slice(&table);
+        Ok(())
+    }
+}
+
+fn write_i16(buf: &mut Vec<u8>, val: i16) {
+    buf.extend_from_slice(&val.to_le_bytes());
+}
+
+/// Writes a section size, which must fit into a non-negative 16-bit number
+fn write_len(buf: &mut Vec<u8>, len: usize) -> Result<(), Error> {
+    let len = i16::try_from(len).map_err(|_| Error::TableTooLarge)?;
+    write_i16(buf, len);
+    Ok(())
+}
+
+/// Converts a string table offset, which must fit into a non-negative 16-bit number
+fn table_offset(off: usize) -> Result<u16, Error> {
+    i16::try_from(off)
+        .map(|off| off as u16)
+        .map_err(|_| Error::TableTooLarge)
+}
+
+fn write_number(buf: &mut Vec<u8>, num: i32, format: Format) {
+    match format {
+        Format::Legacy => write_i16(buf, num.clamp(i16::MIN.into(), i16::MAX.into()) as i16),
+        Format::Number32 => buf.extend_from_slice(&num.to_le_bytes()),
+    }
+}
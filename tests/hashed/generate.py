@@ -0,0 +1,117 @@
+#!/usr/bin/env python3
+"""Generates the hashed terminfo databases in this directory from the compiled
+entries in tests/ with Berkeley DB (libdb 5.3) like `tic` does for ncurses builds
+configured with --with-hashed-db:
+
+* the primary name maps to a 0 byte followed by the compiled entry
+* every alias maps to a 2 byte followed by the primary name
+
+terminfo.db uses the default page size and the native (little endian) byte order.
+small-pages.db contains a subset of the entries with 512 byte pages in big endian
+byte order so that every entry is stored on overflow pages.
+"""
+
+import ctypes
+import os
+import sys
+
+HERE = os.path.dirname(os.path.abspath(__file__))
+TESTS = os.path.dirname(HERE)
+
+DB_HASH = 1
+O_RDWR_CREAT_TRUNC = os.O_RDWR | os.O_CREAT | os.O_TRUNC
+
+
+class DBT(ctypes.Structure):
+    _fields_ = [("data", ctypes.c_void_p), ("size", ctypes.c_size_t)]
+
+
+class HASHINFO(ctypes.Structure):
+    _fields_ = [
+        ("bsize", ctypes.c_uint),
+        ("ffactor", ctypes.c_uint),
+        ("nelem", ctypes.c_uint),
+        ("cachesize", ctypes.c_uint),
+        ("hash", ctypes.c_void_p),
+        ("lorder", ctypes.c_int),
+    ]
+
+
+CLOSE = ctypes.CFUNCTYPE(ctypes.c_int, ctypes.c_void_p)
+PUT = ctypes.CFUNCTYPE(
+    ctypes.c_int, ctypes.c_void_p, ctypes.POINTER(DBT), ctypes.POINTER(DBT), ctypes.c_uint
+)
+
+
+class DB(ctypes.Structure):
+    _fields_ = [
+        ("type", ctypes.c_int),
+        ("close", CLOSE),
+        ("del_", ctypes.c_void_p),
+        ("get", ctypes.c_void_p),
+        ("put", PUT),
+        ("seq", ctypes.c_void_p),
+        ("sync", ctypes.c_void_p),
+        ("internal", ctypes.c_void_p),
+        ("fd", ctypes.c_void_p),
+    ]
+
+
+libdb = ctypes.CDLL("libdb-5.3.so")
+dbopen = libdb.__db185_open
+dbopen.restype = ctypes.POINTER(DB)
+dbopen.argtypes = [ctypes.c_char_p, ctypes.c_int, ctypes.c_int, ctypes.c_int, ctypes.c_void_p]
+
+
+def entries(names=None):
+    """Returns the entries in tests/ by their primary name. Some entries are stored in
+    multiple files (from different ncurses versions), the file named after the entry
+    is used in that case."""
+    res = {}
+    for file in sorted(os.listdir(TESTS)):
+        path = os.path.join(TESTS, file)
+        if not os.path.isfile(path) or (names is not None and file not in names):
+            continue
+        with open(path, "rb") as f:
+            data = f.read()
+        names_len = int.from_bytes(data[2:4], "little")
+        parts = [part.strip() for part in data[12 : 12 + names_len - 1].decode().split("|")]
+        if parts[0] not in res or file == parts[0]:
+            res[parts[0]] = (parts[1:-1], data)
+    return res
+
+
+def generate(file, info=None, names=None):
+    path = os.path.join(HERE, file)
+    db = dbopen(path.encode(), O_RDWR_CREAT_TRUNC, 0o644, DB_HASH, info)
+    if not db:
+        sys.exit(f"failed to create {path}")
+
+    def put(key, val):
+        key_buf = ctypes.create_string_buffer(key, len(key))
+        val_buf = ctypes.create_string_buffer(val, len(val))
+        key_dbt = DBT(ctypes.cast(key_buf, ctypes.c_void_p), len(key))
+        val_dbt = DBT(ctypes.cast(val_buf, ctypes.c_void_p), len(val))
+        if db.contents.put(db, key_dbt, val_dbt, 0) != 0:
+            sys.exit(f"failed to store {key!r}")
+
+    entries_by_name = entries(names)
+    for name, (_, data) in entries_by_name.items():
+        put(name.encode(), b"\0" + data)
+    # aliases never replace entries or other aliases
+    aliases = set(entries_by_name)
+    for name, (entry_aliases, _) in entries_by_name.items():
+        for alias in entry_aliases:
+            if alias not in aliases:
+                aliases.add(alias)
+                put(alias.encode(), b"\2" + name.encode() + b"\0")
+    db.contents.close(db)
+
+
+generate("terminfo.db")
+small = HASHINFO(bsize=512, lorder=4321)
+generate(
+    "small-pages.db",
+    ctypes.byref(small),
+    {"xterm", "xterm-256color", "st-256color", "linux", "screen", "tmux-256color", "dumb"},
+)